@@ -3,21 +3,47 @@
 //! This module provides the [`AsyncJob`] struct for polling and waiting on
 //! asynchronous operations like PDF splitting.
 
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
+use futures::stream::{self, Stream, StreamExt};
 use log::debug;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
+use crate::client::RenamedClient;
 use crate::error::{RenamedError, Result};
 use crate::models::{JobStatus, JobStatusResponse, PdfSplitResult};
 
-/// Default polling interval for async jobs.
-const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Default delays between polls: fast at first, since most jobs finish within
+/// seconds, then backing off to 2 seconds. The last delay repeats.
+const DEFAULT_POLL_SCHEDULE: [Duration; 9] = [
+    Duration::from_millis(250),
+    Duration::from_millis(250),
+    Duration::from_millis(250),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+];
 
-/// Maximum number of poll attempts (5 minutes at 2s intervals).
-const MAX_POLL_ATTEMPTS: u32 = 150;
+/// How long jobs are polled by default before timing out.
+const DEFAULT_POLL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Seconds the server may hold a long-poll status request open.
+///
+/// Kept below the client's default 30 second request timeout.
+const LONG_POLL_WAIT_SECS: u64 = 25;
 
 /// A callback function that receives progress updates during job polling.
+///
+/// It is called with every status response, so pending jobs can report their
+/// [`queue_position`](JobStatusResponse::queue_position) before any progress.
 pub type ProgressCallback = Box<dyn Fn(&JobStatusResponse) + Send + Sync>;
 
 /// Represents an asynchronous job that can be polled for completion.
@@ -49,40 +75,51 @@ pub type ProgressCallback = Box<dyn Fn(&JobStatusResponse) + Send + Sync>;
 /// # }
 /// ```
 pub struct AsyncJob {
-    /// HTTP client for making requests.
-    client: Arc<reqwest::Client>,
-
-    /// API key for authentication.
-    api_key: String,
+    /// Client whose request path status polls and result downloads go through.
+    client: RenamedClient,
 
     /// URL to poll for job status.
     status_url: String,
 
-    /// Interval between poll attempts.
-    poll_interval: Duration,
+    /// Delays between poll attempts; the last one repeats. Never empty.
+    poll_schedule: Vec<Duration>,
+
+    /// Upper bound of the random delay added to each poll interval.
+    poll_jitter: Duration,
 
     /// Maximum number of poll attempts before timing out.
     max_attempts: u32,
 
+    /// Whether status requests ask the server to hold until the status changes.
+    long_poll: bool,
+
     /// Whether debug logging is enabled.
     debug: bool,
+
+    /// Set by [`cancel()`](AsyncJob::cancel) to stop polling.
+    cancelled: AtomicBool,
+
+    /// Set once a poll observes the job in a terminal state.
+    finished: AtomicBool,
 }
 
 impl AsyncJob {
     /// Creates a new async job.
-    pub(crate) fn new(
-        client: Arc<reqwest::Client>,
-        api_key: String,
-        status_url: String,
-        debug: bool,
-    ) -> Self {
+    pub(crate) fn new(client: RenamedClient, status_url: String) -> Self {
+        let poll_schedule = DEFAULT_POLL_SCHEDULE.to_vec();
+        let max_attempts = attempts_within(&poll_schedule, DEFAULT_POLL_TIMEOUT);
+        let debug = client.is_debug_enabled();
+
         Self {
             client,
-            api_key,
             status_url,
-            poll_interval: DEFAULT_POLL_INTERVAL,
-            max_attempts: MAX_POLL_ATTEMPTS,
+            poll_schedule,
+            poll_jitter: Duration::ZERO,
+            max_attempts,
+            long_poll: false,
             debug,
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
         }
     }
 
@@ -92,22 +129,107 @@ impl AsyncJob {
         self.status_url.rsplit('/').next().unwrap_or("unknown")
     }
 
-    /// Sets a custom polling interval.
+    /// Polls at a fixed interval.
     ///
-    /// The default is 2 seconds.
+    /// Replaces the default schedule, which polls every 250ms at first and
+    /// backs off to every 2 seconds; see [`with_poll_schedule()`](Self::with_poll_schedule).
     pub fn with_poll_interval(mut self, interval: Duration) -> Self {
-        self.poll_interval = interval;
+        self.poll_schedule = vec![interval];
+        self
+    }
+
+    /// Sets the delays between successive polls.
+    ///
+    /// The first delay follows the first poll, the second the second, and so
+    /// on; the last delay repeats once the schedule runs out. An empty
+    /// schedule is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// use std::time::Duration;
+    ///
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let job = client.pdf_split("document.pdf", None).await?.with_poll_schedule(vec![
+    ///     Duration::from_millis(100),
+    ///     Duration::from_millis(500),
+    ///     Duration::from_secs(5),
+    /// ]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_poll_schedule(mut self, schedule: Vec<Duration>) -> Self {
+        if !schedule.is_empty() {
+            self.poll_schedule = schedule;
+        }
+        self
+    }
+
+    /// Adds a random delay of up to `jitter` to each poll interval.
+    ///
+    /// Spreads out polls when many jobs are started at once, so they don't all
+    /// hit the server on the same cadence. The default is no jitter.
+    pub fn with_poll_jitter(mut self, jitter: Duration) -> Self {
+        self.poll_jitter = jitter;
+        self
+    }
+
+    /// Returns the delay after poll number `attempt` (0-based), including any jitter.
+    fn next_poll_delay(&self, attempt: u32) -> Duration {
+        let delay = scheduled_delay(&self.poll_schedule, attempt);
+        if self.poll_jitter.is_zero() {
+            return delay;
+        }
+        let max_nanos = u64::try_from(self.poll_jitter.as_nanos()).unwrap_or(u64::MAX);
+        delay + Duration::from_nanos(fastrand::u64(0..=max_nanos))
+    }
+
+    /// Enables long polling of the status endpoint.
+    ///
+    /// Each status request asks the server to hold the connection (via a
+    /// `wait` query parameter) until the job's status changes or 25 seconds
+    /// pass, which cuts the number of requests for long jobs. Polls that the
+    /// server answers before the poll interval elapses are followed by the
+    /// usual wait, so servers without long-poll support are still polled at
+    /// the normal interval. The client's request timeout must exceed 25
+    /// seconds; the default of 30 seconds does.
+    pub fn with_long_poll(mut self, enabled: bool) -> Self {
+        self.long_poll = enabled;
         self
     }
 
     /// Sets the maximum number of polling attempts.
     ///
-    /// The default is 150 attempts (5 minutes at 2 second intervals).
+    /// The default allows about 5 minutes of polling on the default schedule.
+    /// See [`with_max_status_requests()`](Self::with_max_status_requests).
     pub fn with_max_attempts(mut self, attempts: u32) -> Self {
         self.max_attempts = attempts;
         self
     }
 
+    /// Caps the number of status requests a wait may send.
+    ///
+    /// A hard limit on HTTP calls for accounts where status polls are counted
+    /// or rate limited, independent of the poll interval: each call to
+    /// [`wait()`](Self::wait), [`wait_until_progress()`](Self::wait_until_progress)
+    /// or [`spawn_polling()`](Self::spawn_polling) sends at most `requests`
+    /// status requests, then fails with a [`RenamedError::Job`] naming the
+    /// cap. Same as [`with_max_attempts()`](Self::with_max_attempts); overrides
+    /// [`with_poll_timeout()`](Self::with_poll_timeout) and vice versa.
+    pub fn with_max_status_requests(self, requests: u32) -> Self {
+        self.with_max_attempts(requests)
+    }
+
+    /// Gives up polling after roughly `timeout`.
+    ///
+    /// Converts the timeout into a number of attempts on the current poll
+    /// schedule, so set the interval or schedule first.
+    pub fn with_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.max_attempts = attempts_within(&self.poll_schedule, timeout);
+        self
+    }
+
     /// Returns the status URL for this job.
     pub fn status_url(&self) -> &str {
         &self.status_url
@@ -115,36 +237,35 @@ impl AsyncJob {
 
     /// Fetches the current job status.
     ///
+    /// Sent like any other API request, with the client's retries, request id
+    /// and deadline.
+    ///
     /// # Errors
     ///
     /// Returns an error if the network request fails or the response cannot be parsed.
     pub async fn status(&self) -> Result<JobStatusResponse> {
         let start = Instant::now();
 
-        let response = self
+        let query: &[(&str, u64)] = if self.long_poll {
+            &[("wait", LONG_POLL_WAIT_SECS)]
+        } else {
+            &[]
+        };
+        let body = self
             .client
-            .get(&self.status_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await
-            .map_err(RenamedError::from_reqwest)?;
-
-        let status_code = response.status().as_u16();
+            .get_job_resource(&self.status_url, query, true)
+            .await?;
         let elapsed_ms = start.elapsed().as_millis();
-        let body = response.text().await.map_err(RenamedError::from_reqwest)?;
-
-        if status_code >= 400 {
-            return Err(RenamedError::from_http_status(status_code, Some(&body)));
-        }
 
         let status_response: JobStatusResponse =
             serde_json::from_str(&body).map_err(RenamedError::from_serde)?;
 
         if self.debug {
-            let progress_str = status_response
-                .progress
-                .map(|p| format!(" ({}%)", p))
-                .unwrap_or_default();
+            let progress_str = match (status_response.queue_position, status_response.progress) {
+                (Some(position), _) => format!(" (queue position {})", position),
+                (None, Some(p)) => format!(" ({}%)", p),
+                (None, None) => String::new(),
+            };
             debug!(
                 "[Renamed] Job {}: {}{} ({}ms)",
                 self.extract_job_id(),
@@ -172,7 +293,9 @@ impl AsyncJob {
     ///
     /// # Errors
     ///
-    /// - Returns [`RenamedError::Job`] if the job fails or times out.
+    /// - Returns [`RenamedError::Job`] if the job fails or times out. If the
+    ///   job failed after producing some documents, they are in its `partial`
+    ///   field.
     /// - Returns network errors if polling fails.
     ///
     /// # Example
@@ -183,8 +306,9 @@ impl AsyncJob {
     /// let job = client.pdf_split("document.pdf", None).await?;
     ///
     /// // With progress callback
-    /// let result = job.wait(Some(Box::new(|status| {
-    ///     println!("Progress: {}%", status.progress.unwrap_or(0));
+    /// let result = job.wait(Some(Box::new(|status| match status.queue_position {
+    ///     Some(position) => println!("Queued at position {}", position),
+    ///     None => println!("Progress: {}%", status.progress.unwrap_or(0)),
     /// }))).await?;
     ///
     /// println!("Split into {} documents", result.documents.len());
@@ -192,42 +316,358 @@ impl AsyncJob {
     /// # }
     /// ```
     pub async fn wait(&self, on_progress: Option<ProgressCallback>) -> Result<PdfSplitResult> {
-        for _attempt in 0..self.max_attempts {
-            let status = self.status().await?;
+        let mut state = PollState::default();
+        loop {
+            let status = self.next_poll(&mut state).await?;
 
             // Invoke progress callback if provided
             if let Some(ref callback) = on_progress {
                 callback(&status);
             }
 
-            // Check if job completed successfully
-            if status.status == JobStatus::Completed {
-                return status.result.ok_or_else(|| {
-                    RenamedError::job_error(
-                        "Job completed but no result returned",
-                        Some(status.job_id),
-                    )
-                });
+            if let Some(outcome) = self.outcome(status).await {
+                return outcome;
             }
+        }
+    }
+
+    /// Polls until the job reaches `percent` progress or finishes.
+    ///
+    /// Returns the first status whose progress is at least `percent`, or the
+    /// final status if the job completes first. Useful for staged progress UIs
+    /// that change once a job is partly done.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`RenamedError::Job`] if the job fails or polling times out.
+    /// - Returns network errors if polling fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let job = client.pdf_split("document.pdf", None).await?;
+    ///
+    /// job.wait_until_progress(50).await?;
+    /// println!("Halfway there");
+    ///
+    /// let result = job.wait(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_progress(&self, percent: u8) -> Result<JobStatusResponse> {
+        let mut state = PollState::default();
+        loop {
+            let status = self.next_poll(&mut state).await?;
 
-            // Check if job failed
             if status.status == JobStatus::Failed {
+                return Err(Self::failure_error(status));
+            }
+            if status.status == JobStatus::Completed
+                || status.progress.is_some_and(|p| p >= percent)
+            {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Spawns a task that polls the job, sending each status to `tx`.
+    ///
+    /// The returned handle resolves with the same result as
+    /// [`wait()`](Self::wait). Abort the handle to stop polling. Polling
+    /// continues if the receiver is dropped, so the final result is still
+    /// available through the handle.
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let job = client.pdf_split("document.pdf", None).await?;
+    ///
+    /// let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    /// let handle = job.spawn_polling(tx);
+    ///
+    /// while let Some(status) = rx.recv().await {
+    ///     println!("Progress: {}%", status.progress.unwrap_or(0));
+    /// }
+    ///
+    /// let result = handle.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_polling(
+        self,
+        tx: mpsc::Sender<JobStatusResponse>,
+    ) -> JoinHandle<Result<PdfSplitResult>> {
+        tokio::spawn(async move {
+            let mut state = PollState::default();
+            loop {
+                let status = self.next_poll(&mut state).await?;
+
+                // A closed channel only means nobody is listening for progress
+                let _ = tx.send(status.clone()).await;
+
+                if let Some(outcome) = self.outcome(status).await {
+                    return outcome;
+                }
+            }
+        })
+    }
+
+    /// Returns a stream of the job's statuses, ending once the job finishes.
+    ///
+    /// Each poll yields its status, including the final one. When the stream
+    /// ends, [`JobUpdates::into_result()`] returns the same result as
+    /// [`wait()`](Self::wait). Polling errors, job failures and timeouts also
+    /// end the stream and are reported by `into_result()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// use futures::StreamExt;
+    ///
+    /// let job = client.pdf_split("document.pdf", None).await?;
+    ///
+    /// let mut updates = job.updates();
+    /// while let Some(status) = updates.next().await {
+    ///     println!("Progress: {}%", status.progress.unwrap_or(0));
+    /// }
+    ///
+    /// let result = updates.into_result().await?;
+    /// println!("Split into {} documents", result.documents.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn updates(&self) -> JobUpdates<'_> {
+        let result = Arc::new(Mutex::new(None));
+        let slot = Arc::clone(&result);
+
+        let inner = stream::unfold(PollState::default(), move |mut state| {
+            let slot = Arc::clone(&slot);
+            async move {
+                let record = |outcome| {
+                    *slot.lock().unwrap_or_else(|e| e.into_inner()) = Some(outcome);
+                };
+
+                if slot.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+                    return None;
+                }
+                let status = match self.next_poll(&mut state).await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        record(Err(e));
+                        return None;
+                    }
+                };
+
+                // Record the outcome now; the next call ends the stream
+                if let Some(outcome) = self.outcome(status.clone()).await {
+                    record(outcome);
+                }
+                Some((status, state))
+            }
+        });
+
+        JobUpdates {
+            inner: Box::pin(inner.fuse()),
+            result,
+        }
+    }
+
+    /// Runs one step of a poll loop: waits out the delay after the previous
+    /// poll, then polls.
+    ///
+    /// Every poll loop goes through here, so they all stop after the same
+    /// number of polls. Once the attempts are used up, fails with the timeout
+    /// error without sleeping first. A wait that would end past the client's
+    /// deadline fails with [`RenamedError::Timeout`] instead of sleeping.
+    async fn next_poll(&self, state: &mut PollState) -> Result<JobStatusResponse> {
+        if state.attempt >= self.max_attempts {
+            return Err(self.timeout_error());
+        }
+        if let Some(poll_started) = state.last_poll {
+            let delay = self.pause_after(state.attempt - 1, poll_started);
+            if self.client.deadline().is_some_and(|deadline| {
+                Instant::now()
+                    .checked_add(delay)
+                    .is_none_or(|resume| resume >= deadline)
+            }) {
+                return Err(RenamedError::deadline_exceeded());
+            }
+            tokio::time::sleep(delay).await;
+        }
+
+        state.last_poll = Some(Instant::now());
+        state.attempt += 1;
+        self.poll().await
+    }
+
+    /// Returns how long to wait before the poll after `attempt`, whose loop
+    /// iteration began at `poll_started`.
+    ///
+    /// With long polling, time the server spent holding the request counts
+    /// toward the interval, so a held request is followed by an immediate poll.
+    fn pause_after(&self, attempt: u32, poll_started: Instant) -> Duration {
+        let delay = self.next_poll_delay(attempt);
+        if self.long_poll {
+            delay.saturating_sub(poll_started.elapsed())
+        } else {
+            delay
+        }
+    }
+
+    /// Fetches the status for one iteration of a poll loop.
+    ///
+    /// Fails with [`RenamedError::Cancelled`] if the job was cancelled.
+    async fn poll(&self) -> Result<JobStatusResponse> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Err(RenamedError::Cancelled {
+                message: format!("Job {} was cancelled", self.extract_job_id()).into(),
+            });
+        }
+
+        let status = self.status().await?;
+        if status.status.is_finished() {
+            self.finished.store(true, Ordering::SeqCst);
+        }
+        Ok(status)
+    }
+
+    /// Returns the final result if `status` is terminal, or `None` to keep polling.
+    async fn outcome(&self, status: JobStatusResponse) -> Option<Result<PdfSplitResult>> {
+        match status.status {
+            JobStatus::Completed => Some(self.completed(status).await),
+            JobStatus::Failed => Some(Err(Self::failure_error(status))),
+            _ => None,
+        }
+    }
+
+    /// Builds the result of a completed job.
+    ///
+    /// Large results may not be embedded in the status response; the job then
+    /// references them through `result_url`, which is downloaded and parsed.
+    async fn completed(&self, status: JobStatusResponse) -> Result<PdfSplitResult> {
+        let mut result = match (status.result, status.result_url) {
+            (Some(result), _) => result,
+            (None, Some(url)) => self.fetch_result(&url).await?,
+            (None, None) => {
                 return Err(RenamedError::job_error(
-                    status.error.unwrap_or_else(|| "Job failed".to_string()),
+                    "Job completed but no result returned",
                     Some(status.job_id),
-                ));
+                ))
             }
+        };
+
+        // Fall back to the job-level values when the result omits them
+        result.credits_used = result.credits_used.or(status.credits_used);
+        result.mode_used = result.mode_used.or(status.mode_used);
+        Ok(result)
+    }
+
+    /// Downloads and parses a result payload referenced by the job status.
+    ///
+    /// Sent the same way as [`status()`](Self::status), except that the API
+    /// key is only sent when the URL is on the same origin as the status URL,
+    /// so pre-signed storage URLs never receive it.
+    async fn fetch_result(&self, url: &str) -> Result<PdfSplitResult> {
+        let authenticate = same_origin(url, &self.status_url);
+        let body = self.client.get_job_resource(url, &[], authenticate).await?;
+
+        if self.debug {
+            debug!(
+                "[Renamed] Job {}: downloaded result ({} bytes)",
+                self.extract_job_id(),
+                body.len()
+            );
+        }
+
+        serde_json::from_str(&body).map_err(RenamedError::from_serde)
+    }
+
+    /// Error for a job that reported [`JobStatus::Failed`].
+    ///
+    /// Keeps any documents the job finished before failing, so callers can
+    /// salvage them from [`RenamedError::Job`]'s `partial` field.
+    fn failure_error(status: JobStatusResponse) -> RenamedError {
+        RenamedError::Job {
+            message: status.error.map_or("Job failed".into(), Into::into),
+            job_id: Some(status.job_id),
+            partial: status
+                .result
+                .filter(|result| !result.documents.is_empty())
+                .map(Box::new),
+        }
+    }
+
+    /// Error returned when a poll loop exhausts its attempts.
+    ///
+    /// The limit is a number of status requests, so the message says so
+    /// rather than implying a time limit.
+    fn timeout_error(&self) -> RenamedError {
+        RenamedError::job_error(
+            format!(
+                "Job polling timeout exceeded: gave up after {} status requests",
+                self.max_attempts
+            ),
+            Some(self.extract_job_id().to_string()),
+        )
+    }
+
+    /// Cancels the job locally, stopping any in-progress [`wait()`](Self::wait).
+    ///
+    /// A pending `wait()` returns [`RenamedError::Cancelled`] at its next poll,
+    /// and later calls to `wait()` return it immediately. The API has no
+    /// endpoint to abort a job, so processing may still finish server-side.
+    /// Cancelling an already cancelled job succeeds.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`RenamedError::Job`] if the job has already completed or failed.
+    /// - Returns network errors if checking the job's status fails.
+    pub async fn cancel(&self) -> Result<()> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Ok(());
+        }
 
-            // Wait before next poll
-            tokio::time::sleep(self.poll_interval).await;
+        if !self.finished.load(Ordering::SeqCst) {
+            let status = self.status().await?;
+            if !status.status.is_finished() {
+                self.cancelled.store(true, Ordering::SeqCst);
+                if self.debug {
+                    debug!("[Renamed] Job {}: cancelled", self.extract_job_id());
+                }
+                return Ok(());
+            }
+            self.finished.store(true, Ordering::SeqCst);
         }
 
         Err(RenamedError::job_error(
-            "Job polling timeout exceeded",
-            None,
+            "Job already finished",
+            Some(self.extract_job_id().to_string()),
         ))
     }
 
+    /// Fetches the status once and returns the result of a finished job.
+    ///
+    /// Fails with [`RenamedError::Job`] if the job failed or hasn't completed yet.
+    pub(crate) async fn completed_result(&self) -> Result<PdfSplitResult> {
+        let status = self.status().await?;
+        let current = status.status;
+        let job_id = status.job_id.clone();
+        self.outcome(status).await.unwrap_or_else(|| {
+            Err(RenamedError::job_error(
+                format!("Job has not completed yet (status: {})", current),
+                Some(job_id),
+            ))
+        })
+    }
+
     /// Waits for the job to complete without a progress callback.
     ///
     /// This is a convenience method equivalent to `wait(None)`.
@@ -236,12 +676,128 @@ impl AsyncJob {
     }
 }
 
+/// Progress of one poll loop, advanced by [`AsyncJob::next_poll()`].
+#[derive(Debug, Default)]
+struct PollState {
+    /// Polls sent so far.
+    attempt: u32,
+
+    /// When the previous poll was sent, if any.
+    last_poll: Option<Instant>,
+}
+
+/// Stream of a job's statuses, returned by [`AsyncJob::updates()`].
+///
+/// Yields each polled [`JobStatusResponse`] and ends once the job finishes.
+/// Call [`into_result()`](Self::into_result) afterwards for the final result.
+pub struct JobUpdates<'a> {
+    inner: Pin<Box<dyn Stream<Item = JobStatusResponse> + Send + 'a>>,
+    result: Arc<Mutex<Option<Result<PdfSplitResult>>>>,
+}
+
+impl JobUpdates<'_> {
+    /// Returns the job's final result.
+    ///
+    /// If the stream hasn't ended yet, keeps polling until it does, skipping
+    /// the remaining statuses.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`AsyncJob::wait()`].
+    pub async fn into_result(mut self) -> Result<PdfSplitResult> {
+        while self.next().await.is_some() {}
+
+        self.result
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .expect("finished job updates always record a result")
+    }
+}
+
+impl Stream for JobUpdates<'_> {
+    type Item = JobStatusResponse;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl std::fmt::Debug for JobUpdates<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobUpdates").finish_non_exhaustive()
+    }
+}
+
+/// Cancels all the given jobs concurrently.
+///
+/// Returns one result per job, in the same order, so jobs that couldn't be
+/// cancelled (e.g. because they already finished) can be reported. See
+/// [`AsyncJob::cancel()`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(jobs: Vec<renamed::AsyncJob>) {
+/// for (job, outcome) in jobs.iter().zip(renamed::cancel_all(&jobs).await) {
+///     if let Err(err) = outcome {
+///         eprintln!("Could not cancel {}: {}", job.status_url(), err);
+///     }
+/// }
+/// # }
+/// ```
+pub async fn cancel_all(jobs: &[AsyncJob]) -> Vec<Result<()>> {
+    futures::future::join_all(jobs.iter().map(AsyncJob::cancel)).await
+}
+
+/// Returns true if both URLs share a scheme, host, and port.
+fn same_origin(a: &str, b: &str) -> bool {
+    match (reqwest::Url::parse(a), reqwest::Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    }
+}
+
+/// Returns the delay after poll number `attempt`, repeating the last entry.
+fn scheduled_delay(schedule: &[Duration], attempt: u32) -> Duration {
+    let index = usize::try_from(attempt).unwrap_or(usize::MAX);
+    schedule
+        .get(index)
+        .or(schedule.last())
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Returns how many polls on `schedule` it takes for the delays to add up to `timeout`.
+fn attempts_within(schedule: &[Duration], timeout: Duration) -> u32 {
+    // Treat zero delays as 1ms so a zero schedule still has a finite count
+    let min_delay = Duration::from_millis(1);
+    let mut elapsed = Duration::ZERO;
+    let mut attempts: u128 = 0;
+
+    for delay in schedule {
+        if elapsed >= timeout {
+            break;
+        }
+        elapsed += (*delay).max(min_delay);
+        attempts += 1;
+    }
+    if elapsed < timeout {
+        let last = schedule.last().copied().unwrap_or_default().max(min_delay);
+        attempts += (timeout - elapsed).as_nanos().div_ceil(last.as_nanos());
+    }
+
+    u32::try_from(attempts.max(1)).unwrap_or(u32::MAX)
+}
+
 impl std::fmt::Debug for AsyncJob {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AsyncJob")
             .field("status_url", &self.status_url)
-            .field("poll_interval", &self.poll_interval)
+            .field("poll_schedule", &self.poll_schedule)
+            .field("poll_jitter", &self.poll_jitter)
             .field("max_attempts", &self.max_attempts)
+            .field("long_poll", &self.long_poll)
             .finish()
     }
 }
@@ -249,32 +805,363 @@ impl std::fmt::Debug for AsyncJob {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock_server::{MockResponse, MockServer};
 
     #[test]
     fn test_async_job_builder() {
-        let client = Arc::new(reqwest::Client::new());
         let job = AsyncJob::new(
-            client,
-            "test_key".to_string(),
+            RenamedClient::new("test_key"),
             "https://example.com/status".to_string(),
-            false,
         )
         .with_poll_interval(Duration::from_secs(5))
         .with_max_attempts(10);
 
-        assert_eq!(job.poll_interval, Duration::from_secs(5));
+        assert_eq!(job.poll_schedule, vec![Duration::from_secs(5)]);
         assert_eq!(job.max_attempts, 10);
         assert_eq!(job.status_url(), "https://example.com/status");
+
+        let job = job.with_poll_timeout(Duration::from_secs(61));
+        assert_eq!(job.max_attempts, 13);
+    }
+
+    #[test]
+    fn test_long_poll_counts_held_time_toward_interval() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "https://example.com/status".to_string(),
+        )
+        .with_poll_interval(Duration::from_millis(50));
+        let held_since = Instant::now() - Duration::from_secs(1);
+
+        assert_eq!(job.pause_after(0, held_since), Duration::from_millis(50));
+
+        let job = job.with_long_poll(true);
+        assert_eq!(job.pause_after(0, held_since), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_poll_schedule_starts_fast_then_backs_off() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "https://example.com/status".to_string(),
+        );
+        assert_eq!(job.next_poll_delay(0), Duration::from_millis(250));
+        assert_eq!(job.next_poll_delay(8), Duration::from_secs(2));
+        assert_eq!(job.next_poll_delay(1000), Duration::from_secs(2));
+        // 4 x 250ms + 2 x 500ms + 2 x 1s = 4s, then 148 polls at 2s
+        assert_eq!(job.max_attempts, 156);
+
+        let job = job
+            .with_poll_schedule(vec![Duration::from_millis(100), Duration::from_secs(1)])
+            .with_poll_schedule(Vec::new());
+        assert_eq!(job.next_poll_delay(0), Duration::from_millis(100));
+        assert_eq!(job.next_poll_delay(5), Duration::from_secs(1));
+
+        let job = job.with_poll_timeout(Duration::from_millis(2050));
+        assert_eq!(job.max_attempts, 3);
+    }
+
+    #[test]
+    fn test_poll_jitter_stays_within_bounds() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "https://example.com/status".to_string(),
+        )
+        .with_poll_interval(Duration::from_secs(2));
+
+        assert_eq!(job.next_poll_delay(0), Duration::from_secs(2));
+
+        let job = job.with_poll_jitter(Duration::from_millis(500));
+        for attempt in 0..100 {
+            let delay = job.next_poll_delay(attempt);
+            assert!(delay >= Duration::from_secs(2));
+            assert!(delay <= Duration::from_millis(2500));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_request_cap_is_reported_in_error() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "http://127.0.0.1:1/status/abc123".to_string(),
+        )
+        .with_max_status_requests(0);
+
+        match job.wait(None).await {
+            Err(RenamedError::Job {
+                message, job_id, ..
+            }) => {
+                assert!(message.contains("0 status requests"));
+                assert_eq!(job_id.as_deref(), Some("abc123"));
+            }
+            other => panic!("expected job error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_job_stops_waiting() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "http://127.0.0.1:1/status/abc123".to_string(),
+        );
+        job.cancelled.store(true, Ordering::SeqCst);
+
+        assert!(job.cancel().await.is_ok());
+        assert!(matches!(
+            job.wait(None).await,
+            Err(RenamedError::Cancelled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_updates_yield_each_status_then_result() {
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(200, r#"{"jobId": "job_1", "status": "processing", "progress": 50}"#),
+            MockResponse::new(200, r#"{"jobId": "job_1", "status": "completed", "progress": 100, "result": {"originalFilename": "scan.pdf", "documents": [], "totalPages": 2}}"#),
+        ]);
+
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            format!("http://{}/status/job_1", server.addr()),
+        )
+        .with_poll_interval(Duration::from_millis(10));
+
+        let mut updates = job.updates();
+        let mut progress = Vec::new();
+        while let Some(status) = updates.next().await {
+            progress.push(status.progress);
+        }
+        assert_eq!(progress, vec![Some(50), Some(100)]);
+
+        let result = updates.into_result().await.unwrap();
+        assert_eq!(result.total_pages, 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out_without_sleeping_after_last_poll() {
+        let server = MockServer::bind().await.serve(vec![MockResponse::new(
+            200,
+            r#"{"jobId": "job_1", "status": "processing"}"#,
+        )]);
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            format!("http://{}/status/job_1", server.addr()),
+        )
+        .with_poll_interval(Duration::from_secs(60))
+        .with_max_attempts(1);
+
+        let outcome = tokio::time::timeout(Duration::from_secs(5), job.wait(None)).await;
+        assert!(matches!(outcome, Ok(Err(RenamedError::Job { .. }))));
+    }
+
+    #[tokio::test]
+    async fn test_polling_stops_at_client_deadline() {
+        let server = MockServer::bind().await.serve(vec![MockResponse::new(
+            200,
+            r#"{"jobId": "job_1", "status": "processing"}"#,
+        )]);
+        let client = RenamedClient::new("test_key")
+            .with_deadline(Instant::now() + Duration::from_millis(500));
+        let job = AsyncJob::new(client, format!("http://{}/status/job_1", server.addr()))
+            .with_poll_interval(Duration::from_secs(60));
+
+        let outcome = tokio::time::timeout(Duration::from_secs(5), job.wait(None)).await;
+        assert!(matches!(outcome, Ok(Err(RenamedError::Timeout { .. }))));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_progress_returns_first_status_at_percent() {
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(
+                200,
+                r#"{"jobId": "job_1", "status": "processing", "progress": 20}"#,
+            ),
+            MockResponse::new(
+                200,
+                r#"{"jobId": "job_1", "status": "processing", "progress": 60}"#,
+            ),
+        ]);
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            format!("http://{}/status/job_1", server.addr()),
+        )
+        .with_poll_interval(Duration::from_millis(10));
+
+        let status = job.wait_until_progress(50).await.unwrap();
+        assert_eq!(status.progress, Some(60));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_progress_stops_at_completion_or_failure() {
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(
+                200,
+                r#"{"jobId": "job_1", "status": "completed", "progress": 40}"#,
+            ),
+            MockResponse::new(
+                200,
+                r#"{"jobId": "job_1", "status": "failed", "error": "Unreadable page"}"#,
+            ),
+        ]);
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            format!("http://{}/status/job_1", server.addr()),
+        );
+
+        // A completed job is returned even below the requested progress
+        let status = job.wait_until_progress(90).await.unwrap();
+        assert_eq!(status.status, JobStatus::Completed);
+
+        match job.wait_until_progress(90).await {
+            Err(RenamedError::Job { message, .. }) => assert_eq!(message, "Unreadable page"),
+            other => panic!("expected job error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_updates_report_cancellation_from_into_result() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "http://127.0.0.1:1/status/abc123".to_string(),
+        );
+        job.cancelled.store(true, Ordering::SeqCst);
+
+        let mut updates = job.updates();
+        assert!(updates.next().await.is_none());
+        assert!(matches!(
+            updates.into_result().await,
+            Err(RenamedError::Cancelled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_polling_reports_cancellation_through_handle() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "http://127.0.0.1:1/status/abc123".to_string(),
+        );
+        job.cancelled.store(true, Ordering::SeqCst);
+
+        let (tx, mut rx) = mpsc::channel(4);
+        let handle = job.spawn_polling(tx);
+
+        assert!(rx.recv().await.is_none());
+        assert!(matches!(
+            handle.await.unwrap(),
+            Err(RenamedError::Cancelled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_finished_job_fails() {
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "http://127.0.0.1:1/status/abc123".to_string(),
+        );
+        job.finished.store(true, Ordering::SeqCst);
+
+        let outcomes = cancel_all(std::slice::from_ref(&job)).await;
+        match &outcomes[..] {
+            [Err(RenamedError::Job { job_id, .. })] => {
+                assert_eq!(job_id.as_deref(), Some("abc123"));
+            }
+            other => panic!("Expected Job error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_failure_error_keeps_partial_result() {
+        let status: JobStatusResponse = serde_json::from_str(
+            r#"{"jobId": "job_1", "status": "failed", "error": "Out of memory", "result": {
+                "originalFilename": "scan.pdf", "totalPages": 10, "documents": [
+                    {"index": 0, "filename": "a.pdf", "pages": "1-8", "downloadUrl": "https://example.com/a", "size": 10}]}}"#,
+        )
+        .unwrap();
+
+        match AsyncJob::failure_error(status) {
+            RenamedError::Job {
+                message,
+                job_id,
+                partial,
+            } => {
+                assert_eq!(message, "Out of memory");
+                assert_eq!(job_id.as_deref(), Some("job_1"));
+                assert_eq!(partial.unwrap().documents.len(), 1);
+            }
+            other => panic!("Expected Job error, got {:?}", other),
+        }
+
+        let status: JobStatusResponse =
+            serde_json::from_str(r#"{"jobId": "job_2", "status": "failed"}"#).unwrap();
+        assert!(matches!(
+            AsyncJob::failure_error(status),
+            RenamedError::Job { partial: None, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_completed_job_downloads_referenced_result() {
+        let server = MockServer::bind().await.serve(vec![MockResponse::new(
+            200,
+            r#"{"originalFilename": "scan.pdf", "documents": [], "totalPages": 3}"#,
+        )]);
+
+        let job = AsyncJob::new(
+            RenamedClient::new("test_key"),
+            "https://example.com/status/job_1".to_string(),
+        );
+        let status: JobStatusResponse = serde_json::from_str(&format!(
+            r#"{{"jobId": "job_1", "status": "completed", "creditsUsed": 3, "resultUrl": "http://{}/results/job_1.json"}}"#,
+            server.addr()
+        ))
+        .unwrap();
+
+        let result = job.outcome(status).await.unwrap().unwrap();
+        assert_eq!(result.total_pages, 3);
+        assert_eq!(result.credits_used, Some(3));
+
+        // The result lives on another origin, so the API key isn't sent there
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0].to_lowercase().contains("authorization"));
+    }
+
+    #[tokio::test]
+    async fn test_status_polls_are_retried_like_api_calls() {
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(500, r#"{"error": "Internal error"}"#),
+            MockResponse::new(200, r#"{"jobId": "job_1", "status": "processing"}"#),
+        ]);
+        let client = RenamedClient::builder("test_key")
+            .retry_on(crate::RetryPolicy::all())
+            .build();
+        let job = AsyncJob::new(client, format!("http://{}/status/job_1", server.addr()));
+
+        let status = job.status().await.unwrap();
+        assert_eq!(status.status, JobStatus::Processing);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_same_origin() {
+        assert!(same_origin(
+            "https://www.renamed.to/api/v1/pdf-split/status/1",
+            "https://www.renamed.to/results/1.json"
+        ));
+        assert!(!same_origin(
+            "https://www.renamed.to/status/1",
+            "https://storage.example.com/results/1.json"
+        ));
+        assert!(!same_origin("not a url", "not a url"));
     }
 
     #[test]
     fn test_extract_job_id() {
-        let client = Arc::new(reqwest::Client::new());
         let job = AsyncJob::new(
-            client,
-            "test_key".to_string(),
+            RenamedClient::new("test_key"),
             "https://example.com/status/abc123".to_string(),
-            false,
         );
 
         assert_eq!(job.extract_job_id(), "abc123");
@@ -0,0 +1,469 @@
+//! Batch operations over many files.
+//!
+//! [`RenamedClient::rename_batch()`](crate::RenamedClient::rename_batch) renames
+//! a set of files concurrently and collects the outcome of each into a
+//! [`BatchResult`], so a single failure doesn't abort the whole batch.
+//!
+//! For previewing bulk renames,
+//! [`RenamedClient::plan_renames()`](crate::RenamedClient::plan_renames) returns
+//! a list of [`RenamePlan`]s that [`apply_plan()`] later carries out on disk.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::client::RenamedClient;
+use crate::error::{RenamedError, Result};
+use crate::models::{relative_folder, RenameOptions, RenameResult};
+
+/// Maximum number of files processed concurrently by batch methods.
+pub(crate) const BATCH_CONCURRENCY: usize = 4;
+
+/// Options controlling how a batch runs, as opposed to how each file is processed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchOptions {
+    /// Abort the batch on the first error that would fail every file.
+    pub fail_fast: bool,
+}
+
+impl BatchOptions {
+    /// Creates batch options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aborts the batch on the first error that would fail every other file.
+    ///
+    /// These are [`RenamedError::Authentication`] and
+    /// [`RenamedError::InsufficientCredits`]. In-flight uploads are cancelled
+    /// and files that hadn't finished are listed in
+    /// [`BatchResult::skipped`]. Errors that only affect one file, or that
+    /// may go away on retry, never abort the batch.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Returns true if `error` should stop the batch under these options.
+    pub(crate) fn aborts_on(&self, error: &RenamedError) -> bool {
+        self.fail_fast
+            && matches!(
+                error,
+                RenamedError::Authentication { .. } | RenamedError::InsufficientCredits { .. }
+            )
+    }
+}
+
+/// Outcome of a batch rename, with per-file results.
+///
+/// Successes and failures are kept in the order the files were submitted.
+#[derive(Debug)]
+pub struct BatchResult {
+    /// Files that were renamed successfully.
+    pub succeeded: Vec<(PathBuf, RenameResult)>,
+
+    /// Files that failed, with the error for each.
+    pub failed: Vec<(PathBuf, RenamedError)>,
+
+    /// Files that were not processed because the batch was aborted by
+    /// [`BatchOptions::with_fail_fast()`].
+    pub skipped: Vec<PathBuf>,
+
+    /// Options the batch was run with, reused by [`retry_failed()`](Self::retry_failed).
+    options: Option<RenameOptions>,
+
+    /// Batch options the batch was run with, reused by [`retry_failed()`](Self::retry_failed).
+    batch_options: BatchOptions,
+}
+
+impl BatchResult {
+    pub(crate) fn new(options: Option<RenameOptions>, batch_options: BatchOptions) -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+            skipped: Vec::new(),
+            options,
+            batch_options,
+        }
+    }
+
+    pub(crate) fn push(&mut self, path: PathBuf, result: Result<RenameResult>) {
+        match result {
+            Ok(result) => self.succeeded.push((path, result)),
+            Err(err) => self.failed.push((path, err)),
+        }
+    }
+
+    /// Returns true if every file in the batch succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Returns true if the batch was aborted by [`BatchOptions::with_fail_fast()`].
+    pub fn is_aborted(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+
+    /// Returns the paths of the files that were renamed successfully.
+    ///
+    /// Persist these to resume an interrupted batch later with
+    /// [`RenamedClient::rename_batch_resume()`].
+    pub fn completed_paths(&self) -> Vec<PathBuf> {
+        self.succeeded
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Returns the paths of the files that failed.
+    pub fn failed_paths(&self) -> Vec<PathBuf> {
+        self.failed.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// Re-runs the failed and skipped files and merges the outcome into this
+    /// result.
+    ///
+    /// Files that succeed on retry move to [`succeeded`](Self::succeeded);
+    /// files that fail again stay in [`failed`](Self::failed) with their new
+    /// error. The batch's original options are reused.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let mut batch = client.rename_batch(["a.pdf", "b.pdf", "c.pdf"], None).await;
+    ///
+    /// for _ in 0..3 {
+    ///     if batch.is_complete() {
+    ///         break;
+    ///     }
+    ///     batch = batch.retry_failed(&client).await;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retry_failed(self, client: &RenamedClient) -> BatchResult {
+        if self.is_complete() {
+            return self;
+        }
+
+        let mut paths = self.failed_paths();
+        paths.extend(self.skipped);
+        let retried = client
+            .rename_batch_with(paths, self.options.clone(), self.batch_options)
+            .await;
+
+        let mut succeeded = self.succeeded;
+        succeeded.extend(retried.succeeded);
+
+        BatchResult {
+            succeeded,
+            failed: retried.failed,
+            skipped: retried.skipped,
+            options: self.options,
+            batch_options: self.batch_options,
+        }
+    }
+}
+
+/// A planned rename of one file, produced by
+/// [`RenamedClient::plan_renames()`](crate::RenamedClient::plan_renames).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenamePlan {
+    /// Current path of the file.
+    pub original: PathBuf,
+
+    /// Suggested new filename.
+    pub suggested: String,
+
+    /// Suggested folder, relative to the file's current directory.
+    pub folder: Option<String>,
+
+    /// Confidence score (0.0 - 1.0) of the suggestion.
+    pub confidence: Option<f64>,
+}
+
+impl RenamePlan {
+    pub(crate) fn new(original: PathBuf, result: RenameResult) -> Self {
+        Self {
+            original,
+            suggested: result.suggested_filename,
+            folder: result.folder_path,
+            confidence: result.confidence,
+        }
+    }
+
+    /// Returns the path the file will be moved to.
+    ///
+    /// The target is the suggested folder and filename, resolved against the
+    /// file's current directory. Path separators in the suggested filename and
+    /// `.`/`..` folder segments are ignored, so the target never escapes that
+    /// directory.
+    pub fn target(&self) -> PathBuf {
+        let mut target = self
+            .original
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        if let Some(folder) = self.folder.as_deref().and_then(relative_folder) {
+            target.push(folder);
+        }
+
+        let filename = Path::new(&self.suggested)
+            .file_name()
+            .or_else(|| self.original.file_name())
+            .unwrap_or_default();
+        target.join(filename)
+    }
+}
+
+/// Moves each file in `plan` to its [`target()`](RenamePlan::target).
+///
+/// Creates suggested folders as needed and returns the new paths, in plan
+/// order. Files whose target equals their current path are left in place.
+///
+/// The whole plan is checked before the first file is moved, so a plan that
+/// would overwrite files is rejected without touching the disk.
+///
+/// # Errors
+///
+/// Returns [`RenamedError::InvalidValue`] if two entries share a target, with
+/// the shared targets under `details["collisions"]`, and [`RenamedError::File`]
+/// if a target already exists and isn't moved away by the plan itself. A move
+/// that fails midway is not rolled back: files before the failing entry have
+/// already been moved.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> Result<(), renamed::RenamedError> {
+/// # let client = renamed::RenamedClient::new("api_key");
+/// let plan = client.plan_renames(["scan1.pdf", "scan2.pdf"], None).await?;
+///
+/// for entry in &plan {
+///     println!("{} -> {}", entry.original.display(), entry.target().display());
+/// }
+///
+/// let moved = renamed::apply_plan(&plan).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn apply_plan(plan: &[RenamePlan]) -> Result<Vec<PathBuf>> {
+    check_plan(plan).await?;
+
+    let mut moved = Vec::with_capacity(plan.len());
+
+    for entry in plan {
+        let target = entry.target();
+        if target == entry.original {
+            moved.push(target);
+            continue;
+        }
+
+        let exists = tokio::fs::try_exists(&target).await.map_err(|e| {
+            RenamedError::from_io(e, format!("Failed to check {}", target.display()))
+        })?;
+        if exists {
+            return Err(RenamedError::File {
+                message: format!("Target already exists: {}", target.display()).into(),
+                source: None,
+            });
+        }
+
+        if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                RenamedError::from_io(e, format!("Failed to create {}", parent.display()))
+            })?;
+        }
+
+        tokio::fs::rename(&entry.original, &target)
+            .await
+            .map_err(|e| {
+                RenamedError::from_io(
+                    e,
+                    format!(
+                        "Failed to move {} to {}",
+                        entry.original.display(),
+                        target.display()
+                    ),
+                )
+            })?;
+        moved.push(target);
+    }
+
+    Ok(moved)
+}
+
+/// Rejects a plan that moves several files to one target or onto an existing file.
+async fn check_plan(plan: &[RenamePlan]) -> Result<()> {
+    let mut sources: HashMap<PathBuf, &Path> = HashMap::new();
+    let mut collisions = Vec::new();
+    for entry in plan {
+        let target = entry.target();
+        match sources.get(&target) {
+            Some(source) if *source != entry.original.as_path() => {
+                collisions.push(target.display().to_string());
+            }
+            _ => {
+                sources.insert(target, &entry.original);
+            }
+        }
+    }
+    if !collisions.is_empty() {
+        collisions.sort();
+        collisions.dedup();
+        return Err(RenamedError::InvalidValue {
+            message: format!(
+                "Plan moves several files to the same target: {}",
+                collisions.join(", ")
+            )
+            .into(),
+            value: None,
+            details: Some(HashMap::from([(
+                "collisions".to_string(),
+                serde_json::json!(collisions),
+            )])),
+        });
+    }
+
+    // A target may exist if the plan moves that file away first
+    let originals: HashSet<&Path> = plan.iter().map(|entry| entry.original.as_path()).collect();
+    for (target, source) in &sources {
+        if target == source || originals.contains(target.as_path()) {
+            continue;
+        }
+        let exists = tokio::fs::try_exists(target).await.map_err(|e| {
+            RenamedError::from_io(e, format!("Failed to check {}", target.display()))
+        })?;
+        if exists {
+            return Err(RenamedError::File {
+                message: format!("Target already exists: {}", target.display()).into(),
+                source: None,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_fast_only_aborts_on_batch_wide_errors() {
+        let options = BatchOptions::new().with_fail_fast(true);
+
+        assert!(options.aborts_on(&RenamedError::from_http_status(401, None)));
+        assert!(options.aborts_on(&RenamedError::from_http_status(402, None)));
+        assert!(!options.aborts_on(&RenamedError::from_http_status(422, None)));
+        assert!(!options.aborts_on(&RenamedError::from_http_status(429, None)));
+        assert!(!BatchOptions::new().aborts_on(&RenamedError::from_http_status(401, None)));
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_reruns_only_failures() {
+        let client = RenamedClient::new("rt_test");
+        let done: RenameResult = serde_json::from_str(
+            r#"{"originalFilename": "a.pdf", "suggestedFilename": "A.pdf", "folderPath": null, "confidence": null}"#,
+        )
+        .unwrap();
+
+        let mut batch = BatchResult::new(None, BatchOptions::default());
+        batch.push(PathBuf::from("a.pdf"), Ok(done));
+        batch.push(
+            PathBuf::from("/nonexistent/b.pdf"),
+            Err(RenamedError::Timeout {
+                message: "timed out".into(),
+                elapsed: None,
+            }),
+        );
+
+        let batch = batch.retry_failed(&client).await;
+
+        assert_eq!(batch.succeeded.len(), 1);
+        assert_eq!(batch.succeeded[0].0, PathBuf::from("a.pdf"));
+        assert_eq!(
+            batch.failed_paths(),
+            vec![PathBuf::from("/nonexistent/b.pdf")]
+        );
+        assert!(matches!(batch.failed[0].1, RenamedError::File { .. }));
+        assert!(!batch.is_complete());
+    }
+
+    #[test]
+    fn test_rename_plan_target_stays_in_directory() {
+        let plan = RenamePlan {
+            original: PathBuf::from("inbox/scan.pdf"),
+            suggested: "../Invoice_2024.pdf".to_string(),
+            folder: Some("/Invoices/../2024/".to_string()),
+            confidence: Some(0.9),
+        };
+
+        assert_eq!(
+            plan.target(),
+            Path::new("inbox")
+                .join("Invoices")
+                .join("2024")
+                .join("Invoice_2024.pdf")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_plan_moves_files() {
+        let dir = std::env::temp_dir().join(format!("renamed-apply-plan-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let original = dir.join("scan.pdf");
+        tokio::fs::write(&original, b"%PDF").await.unwrap();
+
+        let plan = vec![RenamePlan {
+            original: original.clone(),
+            suggested: "Invoice.pdf".to_string(),
+            folder: Some("Invoices".to_string()),
+            confidence: None,
+        }];
+
+        let moved = apply_plan(&plan).await.unwrap();
+        assert_eq!(moved, vec![dir.join("Invoices").join("Invoice.pdf")]);
+        assert!(!original.exists());
+        assert_eq!(tokio::fs::read(&moved[0]).await.unwrap(), b"%PDF");
+
+        // Re-applying fails instead of overwriting
+        tokio::fs::write(&original, b"%PDF").await.unwrap();
+        assert!(matches!(
+            apply_plan(&plan).await,
+            Err(RenamedError::File { .. })
+        ));
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_apply_plan_rejects_colliding_targets_before_moving() {
+        let dir = std::env::temp_dir().join(format!("renamed-plan-clash-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let entry = |name: &str| RenamePlan {
+            original: dir.join(name),
+            suggested: "Invoice.pdf".to_string(),
+            folder: None,
+            confidence: None,
+        };
+        for name in ["a.pdf", "b.pdf"] {
+            tokio::fs::write(dir.join(name), name).await.unwrap();
+        }
+
+        let result = apply_plan(&[entry("a.pdf"), entry("b.pdf")]).await;
+        let untouched = dir.join("a.pdf").exists() && !dir.join("Invoice.pdf").exists();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        match result {
+            Err(RenamedError::InvalidValue { details, .. }) => {
+                let collisions = &details.unwrap()["collisions"];
+                assert_eq!(collisions.as_array().unwrap().len(), 1);
+            }
+            other => panic!("expected invalid value, got {:?}", other),
+        }
+        assert!(untouched);
+    }
+}
@@ -0,0 +1,155 @@
+//! Circuit breaker for failing fast during API outages.
+//!
+//! After a configured number of consecutive failures the breaker "opens" and
+//! requests fail immediately for a cooldown period. Once the cooldown elapses a
+//! single trial request is let through ("half-open"); its outcome decides
+//! whether the breaker closes again or stays open for another cooldown.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::RenamedError;
+
+/// Failure counts and open/half-open timing for one client's requests.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    /// Consecutive failures that open the circuit.
+    threshold: u32,
+
+    /// How long the circuit stays open before allowing a trial request.
+    cooldown: Duration,
+
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_started_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Creates a closed circuit breaker.
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Returns true if a request may be sent now.
+    ///
+    /// While half-open, only one trial request is admitted per cooldown period.
+    pub fn allow_request(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match state.opened_at {
+            None => true,
+            Some(opened_at) if opened_at.elapsed() >= self.cooldown => {
+                let trial_pending = state
+                    .trial_started_at
+                    .is_some_and(|started| started.elapsed() < self.cooldown);
+                if trial_pending {
+                    false
+                } else {
+                    state.trial_started_at = Some(Instant::now());
+                    true
+                }
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Records the outcome of a request attempt.
+    ///
+    /// Only transport failures and 5xx responses count as failures; other API
+    /// errors prove the service is reachable and close the circuit.
+    pub fn record(&self, outcome: std::result::Result<(), &RenamedError>) {
+        let failed = match outcome {
+            Ok(()) => false,
+            Err(error) => Self::is_failure(error),
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if failed {
+            state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+            if state.trial_started_at.is_some() || state.consecutive_failures >= self.threshold {
+                state.opened_at = Some(Instant::now());
+                state.trial_started_at = None;
+            }
+        } else {
+            *state = BreakerState::default();
+        }
+    }
+
+    /// Returns the error reported while the circuit is open.
+    pub fn open_error(&self) -> RenamedError {
+        RenamedError::Network {
+            message: format!(
+                "Circuit open: failing fast for up to {}s after repeated failures",
+                self.cooldown.as_secs()
+            )
+            .into(),
+            source: None,
+            elapsed: None,
+        }
+    }
+
+    fn is_failure(error: &RenamedError) -> bool {
+        match error {
+            RenamedError::Network { .. } | RenamedError::Timeout { .. } => true,
+            RenamedError::Api { status_code, .. } => *status_code >= 500,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeout() -> RenamedError {
+        RenamedError::Timeout {
+            message: "Request timed out".into(),
+            elapsed: None,
+        }
+    }
+
+    #[test]
+    fn test_opens_after_threshold_consecutive_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record(Err(&timeout()));
+        assert!(breaker.allow_request());
+
+        breaker.record(Err(&timeout()));
+        assert!(!breaker.allow_request());
+    }
+
+    #[test]
+    fn test_client_errors_do_not_open_circuit() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+
+        breaker.record(Err(&RenamedError::from_http_status(401, None)));
+        assert!(breaker.allow_request());
+    }
+
+    #[test]
+    fn test_half_open_admits_single_trial() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        breaker.record(Err(&timeout()));
+        assert!(!breaker.allow_request());
+
+        // Simulate the cooldown elapsing
+        breaker.state.lock().unwrap().opened_at = Some(Instant::now() - Duration::from_secs(61));
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+
+        // A successful trial closes the circuit
+        breaker.record(Ok(()));
+        assert!(breaker.allow_request());
+        assert!(breaker.allow_request());
+    }
+}
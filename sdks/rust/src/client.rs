@@ -24,19 +24,35 @@
 //!
 //! Then initialize it in your main function and set `RUST_LOG=renamed=debug`.
 
-use std::path::Path;
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::collections::HashSet;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
+use bytes::Bytes;
+use futures::future::{AbortHandle, Abortable};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use log::{debug, info, warn};
 use reqwest::multipart::{Form, Part};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
-use crate::async_job::AsyncJob;
+use crate::async_job::{AsyncJob, ProgressCallback};
+use crate::batch::{BatchOptions, BatchResult, RenamePlan, BATCH_CONCURRENCY};
+use crate::circuit_breaker::CircuitBreaker;
 use crate::error::{RenamedError, Result};
+use crate::maintenance::{parse_retry_after, MaintenanceGate};
+use crate::metrics::{CompletionHook, RequestMetrics};
 use crate::models::{
-    ExtractOptions, ExtractResult, PdfSplitOptions, PdfSplitResponse, RenameOptions, RenameResult,
-    User,
+    merge_options, Case, DownloadOptions, ExtractOptions, ExtractResult, PdfSplitOptions,
+    PdfSplitResponse, PdfSplitResult, RenameOptions, RenameResult, SplitDocument, User,
 };
+use crate::pdf;
+use crate::rate_limit::RateLimitInfo;
+use crate::retry::{DefaultRetryStrategy, RetryPolicy, RetryStrategy};
+use crate::retry_budget::RetryBudget;
+use crate::upload_budget::UploadBudget;
 
 /// Default base URL for the renamed.to API.
 const DEFAULT_BASE_URL: &str = "https://www.renamed.to/api/v1";
@@ -47,14 +63,151 @@ const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 /// Default maximum number of retries for failed requests.
 const DEFAULT_MAX_RETRIES: u32 = 2;
 
+/// Chunk size used when streaming an upload from a reader.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Most memory reserved up front for a download of announced length, so a
+/// bogus `Content-Length` can't trigger a huge allocation.
+const MAX_DOWNLOAD_PREALLOCATION: u64 = 8 * 1024 * 1024;
+
+/// Header carrying the caller-generated request id.
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Text fields sent with an upload, in form order.
+type FormFields = Vec<(&'static str, String)>;
+
+/// Generates a fresh request id for each API request.
+#[derive(Clone)]
+struct RequestIdFactory(Arc<dyn Fn() -> String + Send + Sync>);
+
+impl std::fmt::Debug for RequestIdFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RequestIdFactory")
+    }
+}
+
+/// Cached [`User`] shared between clones of a client.
+#[derive(Debug)]
+struct UserCache {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, User)>>,
+}
+
+impl UserCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cached user if it is younger than the TTL.
+    fn get(&self) -> Option<User> {
+        let entry = self.entry.lock().unwrap_or_else(|e| e.into_inner());
+        entry
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, user)| user.clone())
+    }
+
+    fn set(&self, user: User) {
+        *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), user));
+    }
+
+    fn invalidate(&self) {
+        *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Retry bookkeeping for a single request.
+#[derive(Debug, Default)]
+struct Attempts {
+    retries: u32,
+    status_code: Option<u16>,
+}
+
+/// Wraps an SDK operation so it can be cancelled from elsewhere.
+///
+/// Returns the wrapped future and an [`AbortHandle`]. Calling
+/// [`AbortHandle::abort()`] makes the future resolve promptly with
+/// [`RenamedError::Cancelled`], dropping the in-flight HTTP request and closing
+/// its connection. Dropping the future has the same effect on the request; the
+/// handle is useful when the future is owned by another task.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> Result<(), renamed::RenamedError> {
+/// # let client = renamed::RenamedClient::new("api_key");
+/// let (upload, handle) = renamed::cancellable(client.extract("large.pdf", None));
+///
+/// // e.g. from a UI event handler
+/// handle.abort();
+///
+/// assert!(matches!(upload.await, Err(renamed::RenamedError::Cancelled { .. })));
+/// # Ok(())
+/// # }
+/// ```
+pub fn cancellable<F, T>(future: F) -> (impl Future<Output = Result<T>>, AbortHandle)
+where
+    F: Future<Output = Result<T>>,
+{
+    let (handle, registration) = AbortHandle::new_pair();
+    let future = Abortable::new(future, registration);
+
+    let wrapped = async move {
+        future.await.unwrap_or_else(|_| {
+            Err(RenamedError::Cancelled {
+                message: "Operation was aborted".into(),
+            })
+        })
+    };
+
+    (wrapped, handle)
+}
+
+/// Replaces the trailing version segment (`v1`, `v2`, ...) of `base_url`,
+/// or appends `version` if there is none.
+fn with_api_version(base_url: &str, version: &str) -> String {
+    let (prefix, last) = base_url.rsplit_once('/').unwrap_or(("", base_url));
+    let is_version =
+        last.len() > 1 && last.starts_with('v') && last[1..].chars().all(|c| c.is_ascii_digit());
+
+    if is_version && prefix.contains("//") {
+        format!("{}/{}", prefix, version)
+    } else {
+        format!("{}/{}", base_url, version)
+    }
+}
+
 /// Builder for configuring a [`RenamedClient`].
 #[derive(Debug, Clone)]
 pub struct RenamedClientBuilder {
     api_key: String,
     base_url: String,
+    api_version: Option<String>,
     timeout: Duration,
+    connect_timeout: Option<Duration>,
+    follow_redirects: bool,
+    proxy: Option<reqwest::Proxy>,
+    resolve: Vec<(String, SocketAddr)>,
     max_retries: u32,
+    retry_policy: RetryPolicy,
+    retry_strategy: Option<Arc<dyn RetryStrategy>>,
+    circuit_breaker: Option<(u32, Duration)>,
+    retry_budget: Option<u32>,
+    max_inflight_bytes: Option<u64>,
+    user_cache_ttl: Option<Duration>,
+    on_complete: Option<CompletionHook>,
+    request_id_factory: Option<RequestIdFactory>,
     debug: bool,
+    log_bodies: bool,
+    mime_detection: bool,
+    sandbox: bool,
+    accept_language: Option<String>,
+    default_rename_options: Option<RenameOptions>,
+    default_pdf_split_options: Option<PdfSplitOptions>,
+    default_extract_options: Option<ExtractOptions>,
 }
 
 impl RenamedClientBuilder {
@@ -63,9 +216,29 @@ impl RenamedClientBuilder {
         Self {
             api_key: api_key.into(),
             base_url: DEFAULT_BASE_URL.to_string(),
+            api_version: None,
             timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            follow_redirects: true,
+            proxy: None,
+            resolve: Vec::new(),
             max_retries: DEFAULT_MAX_RETRIES,
+            retry_policy: RetryPolicy::default(),
+            retry_strategy: None,
+            circuit_breaker: None,
+            retry_budget: None,
+            max_inflight_bytes: None,
+            user_cache_ttl: None,
+            on_complete: None,
+            request_id_factory: None,
             debug: false,
+            log_bodies: false,
+            mime_detection: true,
+            sandbox: false,
+            accept_language: None,
+            default_rename_options: None,
+            default_pdf_split_options: None,
+            default_extract_options: None,
         }
     }
 
@@ -77,6 +250,27 @@ impl RenamedClientBuilder {
         self
     }
 
+    /// Sets the API version, e.g. `"v2"`, keeping the host of the base URL.
+    ///
+    /// Replaces the version segment at the end of the base URL (`/api/v1`
+    /// becomes `/api/v2`), or appends it if the base URL has none. Applies on
+    /// top of [`base_url()`](Self::base_url) regardless of call order; use
+    /// `base_url` alone for full overrides.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .api_version("v2")
+    ///     .build();
+    /// ```
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into().trim_matches('/').to_string());
+        self
+    }
+
     /// Sets the request timeout.
     ///
     /// Default is 30 seconds.
@@ -85,18 +279,35 @@ impl RenamedClientBuilder {
         self
     }
 
-    /// Sets the maximum number of retries for failed requests.
+    /// Sets the timeout for establishing a connection.
     ///
-    /// Default is 2 retries.
-    pub fn max_retries(mut self, retries: u32) -> Self {
-        self.max_retries = retries;
+    /// Independent of the total request [`timeout()`](Self::timeout), so a short
+    /// connect timeout can fail fast on an unreachable host while a long total
+    /// timeout still allows large uploads to complete. Not set by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::RenamedClient;
+    /// use std::time::Duration;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .timeout(Duration::from_secs(300))
+    ///     .build();
+    /// ```
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
         self
     }
 
-    /// Enables or disables debug logging.
+    /// Sets whether HTTP redirects are followed.
     ///
-    /// When enabled, the client logs HTTP requests, responses, retries, and job polling
-    /// using the `log` crate. To see output, configure a logger like `env_logger`.
+    /// Enabled by default, using reqwest's redirect policy (up to 10 hops,
+    /// with the `Authorization` header dropped when a redirect leaves the
+    /// original host). When disabled, no redirect is followed and any 3xx
+    /// response fails with [`RenamedError::Api`], so requests never reach a
+    /// host other than the one they were sent to.
     ///
     /// # Example
     ///
@@ -104,79 +315,110 @@ impl RenamedClientBuilder {
     /// use renamed::RenamedClient;
     ///
     /// let client = RenamedClient::builder("rt_your_api_key")
-    ///     .with_debug(true)
+    ///     .follow_redirects(false)
     ///     .build();
     /// ```
-    pub fn with_debug(mut self, enabled: bool) -> Self {
-        self.debug = enabled;
+    pub fn follow_redirects(mut self, enabled: bool) -> Self {
+        self.follow_redirects = enabled;
         self
     }
 
-    /// Builds the [`RenamedClient`].
-    pub fn build(self) -> RenamedClient {
-        let client = reqwest::Client::builder()
-            .timeout(self.timeout)
-            .build()
-            .expect("Failed to build HTTP client");
-
-        let renamed_client = RenamedClient {
-            api_key: self.api_key,
-            base_url: self.base_url,
-            max_retries: self.max_retries,
-            debug: self.debug,
-            client: Arc::new(client),
-        };
+    /// Routes all requests through a proxy.
+    ///
+    /// Accepts `http://` and `https://` proxy URLs. `socks5://` and `socks5h://`
+    /// URLs require the `socks` cargo feature:
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// renamed = { version = "0.1", features = ["socks"] }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::Network`] if the URL is invalid or its scheme is
+    /// not supported (e.g. a SOCKS URL without the `socks` feature).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .proxy("http://proxy.internal:3128")?
+    ///     .build();
+    /// # Ok::<(), renamed::RenamedError>(())
+    /// ```
+    pub fn proxy(mut self, url: &str) -> Result<Self> {
+        let proxy = reqwest::Proxy::all(url).map_err(|e| RenamedError::Network {
+            message: format!("Invalid proxy URL: {}", e).into(),
+            source: Some(e),
+            elapsed: None,
+        })?;
+        self.proxy = Some(proxy);
+        Ok(self)
+    }
 
-        if self.debug {
-            info!(
-                "[Renamed] Client initialized (api_key: {}, base_url: {})",
-                renamed_client.mask_api_key(),
-                renamed_client.base_url
-            );
-        }
+    /// Resolves `domain` to `addr` instead of using DNS.
+    ///
+    /// Lets tests point the real hostname at a local mock server, or pin an
+    /// address in split-horizon DNS setups, without changing the base URL. The
+    /// port of `addr` is ignored in favor of the URL's port. Can be called
+    /// multiple times for different domains.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::RenamedClient;
+    /// use std::net::SocketAddr;
+    ///
+    /// let mock: SocketAddr = "127.0.0.1:8443".parse().unwrap();
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .resolve("www.renamed.to", mock)
+    ///     .build();
+    /// ```
+    pub fn resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve.push((domain.into(), addr));
+        self
+    }
 
-        renamed_client
+    /// Sets the maximum number of retries for failed requests.
+    ///
+    /// Default is 2 retries.
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
     }
-}
 
-/// The main client for interacting with the renamed.to API.
-///
-/// # Example
-///
-/// ```rust,no_run
-/// use renamed::RenamedClient;
-///
-/// # async fn example() -> Result<(), renamed::RenamedError> {
-/// let client = RenamedClient::new("rt_your_api_key");
-///
-/// // Get user info
-/// let user = client.get_user().await?;
-/// println!("Credits: {}", user.credits.unwrap_or(0));
-///
-/// // Rename a file
-/// let result = client.rename("invoice.pdf", None).await?;
-/// println!("Suggested: {}", result.suggested_filename);
-/// # Ok(())
-/// # }
-/// ```
-#[derive(Debug, Clone)]
-pub struct RenamedClient {
-    api_key: String,
-    base_url: String,
-    max_retries: u32,
-    debug: bool,
-    client: Arc<reqwest::Client>,
-}
+    /// Selects which classes of failures are retried automatically.
+    ///
+    /// Default is [`RetryPolicy::default()`], which retries network failures and
+    /// timeouts only. Use [`RetryPolicy::all()`] to also retry rate-limit (429)
+    /// and server (5xx) responses, or [`RetryPolicy::none()`] to disable retries.
+    pub fn retry_on(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
 
-impl RenamedClient {
-    /// Creates a new client with the given API key using default settings.
+    /// Replaces the built-in retry behavior with a custom [`RetryStrategy`].
     ///
-    /// For custom configuration, use [`RenamedClient::builder()`] instead.
-    pub fn new(api_key: impl Into<String>) -> Self {
-        RenamedClientBuilder::new(api_key).build()
+    /// The strategy decides whether each failed request is retried and how long
+    /// to wait first. When set, it takes precedence over
+    /// [`max_retries()`](Self::max_retries) and [`retry_on()`](Self::retry_on),
+    /// which only configure the default [`DefaultRetryStrategy`].
+    pub fn retry_strategy(mut self, strategy: Arc<dyn RetryStrategy>) -> Self {
+        self.retry_strategy = Some(strategy);
+        self
     }
 
-    /// Creates a builder for configuring the client.
+    /// Enables a circuit breaker that fails fast while the API is down.
+    ///
+    /// After `threshold` consecutive failed attempts (network errors, timeouts,
+    /// or 5xx responses) the circuit opens, and API requests fail immediately
+    /// with [`RenamedError::Network`] for `cooldown`. After the cooldown a single
+    /// trial request is allowed through: success closes the circuit, failure
+    /// keeps it open for another cooldown.
+    ///
+    /// The breaker state is shared by all clones of the built client.
     ///
     /// # Example
     ///
@@ -185,640 +427,3973 @@ impl RenamedClient {
     /// use std::time::Duration;
     ///
     /// let client = RenamedClient::builder("rt_your_api_key")
-    ///     .timeout(Duration::from_secs(60))
-    ///     .max_retries(3)
+    ///     .circuit_breaker(5, Duration::from_secs(30))
     ///     .build();
     /// ```
-    pub fn builder(api_key: impl Into<String>) -> RenamedClientBuilder {
-        RenamedClientBuilder::new(api_key)
+    pub fn circuit_breaker(mut self, threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker = Some((threshold, cooldown));
+        self
     }
 
-    /// Builds the full URL for an API endpoint.
-    fn build_url(&self, path: &str) -> String {
-        if path.starts_with("http://") || path.starts_with("https://") {
-            return path.to_string();
-        }
-        let path = path.trim_start_matches('/');
-        format!("{}/{}", self.base_url, path)
+    /// Caps retries across all requests at `max_retries_per_sec`.
+    ///
+    /// Per-request retries compound during an outage: many concurrent
+    /// requests each retrying several times multiply the load on a struggling
+    /// API. With a budget, every retry takes one token from a bucket shared by
+    /// all clones of the built client, refilled at `max_retries_per_sec`. Once
+    /// the bucket is empty, failed requests return their error instead of
+    /// retrying. First attempts are never limited. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .retry_budget(10)
+    ///     .build();
+    /// ```
+    pub fn retry_budget(mut self, max_retries_per_sec: u32) -> Self {
+        self.retry_budget = Some(max_retries_per_sec);
+        self
     }
 
-    /// Masks the API key for safe logging.
+    /// Caps the bytes of file uploads in flight at `max_bytes`.
     ///
-    /// Returns format like `rt_...xxxx` (first 3 chars + last 4).
-    fn mask_api_key(&self) -> String {
-        let key = &self.api_key;
-        if key.len() <= 7 {
-            return "***".to_string();
-        }
-        let prefix = &key[..3];
-        let suffix = &key[key.len() - 4..];
-        format!("{}...{}", prefix, suffix)
+    /// Uploads from a path or from bytes are held in memory while they are
+    /// sent, so many concurrent uploads of large files can exhaust it. With a
+    /// cap, each upload reserves its file size from a budget shared by all
+    /// clones of the built client before sending, and waits until enough of
+    /// the budget is free. A file larger than the whole budget waits for all
+    /// other uploads to finish, then runs alone. Streaming uploads via
+    /// [`RenamedClient::rename_from_factory()`] are not buffered and not counted.
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::RenamedClient;
+    ///
+    /// // Keep at most 256 MiB of uploads in memory at once
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .max_inflight_bytes(256 * 1024 * 1024)
+    ///     .build();
+    /// ```
+    pub fn max_inflight_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_inflight_bytes = Some(max_bytes);
+        self
     }
 
-    /// Formats a file size in human-readable format.
-    fn format_size(bytes: usize) -> String {
-        const KB: usize = 1024;
-        const MB: usize = KB * 1024;
-        const GB: usize = MB * 1024;
-
-        if bytes >= GB {
-            format!("{:.1} GB", bytes as f64 / GB as f64)
-        } else if bytes >= MB {
-            format!("{:.1} MB", bytes as f64 / MB as f64)
-        } else if bytes >= KB {
-            format!("{:.1} KB", bytes as f64 / KB as f64)
-        } else {
-            format!("{} B", bytes)
-        }
+    /// Caches the result of [`RenamedClient::get_user()`] for `ttl`.
+    ///
+    /// Within the TTL, `get_user` returns the cached profile without a
+    /// request; [`get_user_fresh()`](RenamedClient::get_user_fresh) always
+    /// fetches. The cache is cleared after every upload (rename, split,
+    /// extract) so the credit balance stays roughly accurate, and is shared
+    /// between clones of the client. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .cache_user(Duration::from_secs(60))
+    ///     .build();
+    /// ```
+    pub fn cache_user(mut self, ttl: Duration) -> Self {
+        self.user_cache_ttl = Some(ttl);
+        self
     }
 
-    /// Extracts the path from a URL for logging.
-    fn extract_path(url: &str) -> &str {
-        // For full URLs, extract the path portion
-        if let Some(idx) = url.find("://") {
-            let after_scheme = &url[idx + 3..];
-            if let Some(path_idx) = after_scheme.find('/') {
-                return &after_scheme[path_idx..];
-            }
-        }
-        // For relative paths, return as-is
-        url
+    /// Registers a hook invoked with [`RequestMetrics`] after every API request.
+    ///
+    /// The hook runs once per request, after any retries, for both successes
+    /// and failures. Use it to feed monitoring, e.g. the share of requests
+    /// that only succeeded after retrying.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .on_complete(|metrics| {
+    ///         if metrics.retries > 0 {
+    ///             println!("{} {} needed {} retries", metrics.method, metrics.url, metrics.retries);
+    ///         }
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn on_complete(mut self, hook: impl Fn(&RequestMetrics) + Send + Sync + 'static) -> Self {
+        self.on_complete = Some(CompletionHook::new(hook));
+        self
     }
 
-    /// Returns whether debug logging is enabled.
-    pub fn is_debug_enabled(&self) -> bool {
-        self.debug
+    /// Sets a factory that generates an `X-Request-Id` header for each request.
+    ///
+    /// The factory is called once per API request (retries reuse the same id),
+    /// so every call can carry a unique id, e.g. one embedding your trace id.
+    /// Job status polls and file downloads get an id too. The generated id is
+    /// included in debug logs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use std::sync::Arc;
+    /// use renamed::RenamedClient;
+    ///
+    /// let trace_id = "4bf92f3577b34da6";
+    /// let counter = AtomicU64::new(0);
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .request_id_factory(Arc::new(move || {
+    ///         format!("{}-{}", trace_id, counter.fetch_add(1, Ordering::Relaxed))
+    ///     }))
+    ///     .build();
+    /// ```
+    pub fn request_id_factory(mut self, factory: Arc<dyn Fn() -> String + Send + Sync>) -> Self {
+        self.request_id_factory = Some(RequestIdFactory(factory));
+        self
     }
 
-    /// Makes an HTTP request with retry logic.
-    async fn request(
-        &self,
-        method: reqwest::Method,
-        path: &str,
-    ) -> Result<reqwest::RequestBuilder> {
-        let url = self.build_url(path);
-        Ok(self
-            .client
-            .request(method, url)
-            .header("Authorization", format!("Bearer {}", self.api_key)))
+    /// Enables or disables debug logging.
+    ///
+    /// When enabled, the client logs HTTP requests, responses, retries, and job polling
+    /// using the `log` crate. To see output, configure a logger like `env_logger`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .with_debug(true)
+    ///     .build();
+    /// ```
+    pub fn with_debug(mut self, enabled: bool) -> Self {
+        self.debug = enabled;
+        self
     }
 
-    /// Executes a request with retry logic and returns the response body.
-    async fn execute_request(
-        &self,
-        request: reqwest::RequestBuilder,
-        method: &str,
-        path: &str,
-    ) -> Result<String> {
-        let mut last_error = None;
-        let start = Instant::now();
-
-        for attempt in 0..=self.max_retries {
-            let req = request.try_clone().ok_or_else(|| RenamedError::Network {
-                message: "Failed to clone request for retry".to_string(),
-                source: None,
-            })?;
-
-            // Log retry attempts (not the first attempt)
-            if attempt > 0 && self.debug {
-                let delay_ms = 100 * (1 << (attempt - 1));
-                warn!(
-                    "[Renamed] Retry attempt {}/{}, waiting {}ms",
-                    attempt, self.max_retries, delay_ms
-                );
-            }
-
-            match req.send().await {
-                Ok(response) => {
-                    let status_code = response.status().as_u16();
-                    let elapsed_ms = start.elapsed().as_millis();
-                    let body = response.text().await.map_err(RenamedError::from_reqwest)?;
-
-                    if self.debug {
-                        debug!(
-                            "[Renamed] {} {} -> {} ({}ms)",
-                            method,
-                            Self::extract_path(path),
-                            status_code,
-                            elapsed_ms
-                        );
-                    }
-
-                    if status_code >= 400 {
-                        return Err(RenamedError::from_http_status(status_code, Some(&body)));
-                    }
-
-                    return Ok(body);
-                }
-                Err(err) => {
-                    last_error = Some(RenamedError::from_reqwest(err));
-                    if attempt < self.max_retries {
-                        // Exponential backoff: 100ms, 200ms, 400ms, ...
-                        let delay = Duration::from_millis(100 * (1 << attempt));
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
-        }
-
-        Err(last_error.unwrap_or_else(|| RenamedError::Network {
-            message: "Request failed after retries".to_string(),
-            source: None,
-        }))
+    /// Enables or disables logging of request fields and response bodies.
+    ///
+    /// Only takes effect together with [`with_debug(true)`](Self::with_debug).
+    /// Logs the multipart form fields of uploads and the full response body
+    /// of API requests at debug level. File contents and the API key are never
+    /// logged. Response bodies may contain extracted document data, so keep
+    /// this off outside of troubleshooting.
+    pub fn log_bodies(mut self, enabled: bool) -> Self {
+        self.log_bodies = enabled;
+        self
     }
 
-    /// Creates a multipart form with a file.
+    /// Disables MIME type detection for uploads.
     ///
-    /// Returns the form and file metadata (filename, size) for logging.
-    async fn create_file_form(
-        &self,
-        file_path: impl AsRef<Path>,
-        fields: Vec<(&str, String)>,
-    ) -> Result<(Form, String, usize)> {
-        let path = file_path.as_ref();
-        let filename = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("file")
-            .to_string();
-
-        let content = tokio::fs::read(path).await.map_err(|e| {
-            RenamedError::from_io(e, format!("Failed to read file: {}", path.display()))
-        })?;
-        let file_size = content.len();
-
-        let mime_type = mime_guess::from_path(path)
-            .first_or_octet_stream()
-            .to_string();
+    /// By default the upload's content type is guessed from its file
+    /// extension. When disabled, every upload is sent as
+    /// `application/octet-stream` and the server detects the type from the
+    /// content. Use this if an extension maps to a type the server rejects.
+    pub fn disable_mime_detection(mut self, disabled: bool) -> Self {
+        self.mime_detection = !disabled;
+        self
+    }
 
-        let file_part = Part::bytes(content)
-            .file_name(filename.clone())
-            .mime_str(&mime_type)
-            .map_err(|e| RenamedError::Network {
-                message: format!("Invalid MIME type: {}", e),
-                source: None,
-            })?;
+    /// Marks the client as a sandbox (test) client.
+    ///
+    /// The API has no sandbox base URL or test-mode header yet, so this is
+    /// currently a no-op apart from [`RenamedClient::is_sandbox()`]: requests
+    /// still go to the configured base URL and consume credits as usual.
+    ///
+    /// For integration tests that must not spend credits, point
+    /// [`base_url()`](Self::base_url) at a local mock server that answers with
+    /// recorded API responses. These deserialize into the same types as in
+    /// production.
+    pub fn sandbox(mut self, enabled: bool) -> Self {
+        self.sandbox = enabled;
+        self
+    }
 
-        let mut form = Form::new().part("file", file_part);
+    /// Sets the `Accept-Language` header sent with every request.
+    ///
+    /// Controls the localization of the API's own responses, such as error
+    /// messages. This is independent of any per-operation document language.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::RenamedClient;
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .accept_language("es")
+    ///     .build();
+    /// ```
+    pub fn accept_language(mut self, language: impl Into<String>) -> Self {
+        self.accept_language = Some(language.into());
+        self
+    }
 
-        for (key, value) in fields {
-            form = form.text(key.to_string(), value);
-        }
+    /// Sets default options applied to every rename call.
+    ///
+    /// Defaults are merged field by field with the options passed to
+    /// [`RenamedClient::rename()`]: any field set on the per-call options wins,
+    /// and unset fields fall back to these defaults. Passing `None` to a call
+    /// uses the defaults as-is.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::{RenamedClient, RenameOptions};
+    ///
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .default_rename_options(RenameOptions::new().with_template("{date}_{vendor}"))
+    ///     .build();
+    /// ```
+    pub fn default_rename_options(mut self, options: RenameOptions) -> Self {
+        self.default_rename_options = Some(options);
+        self
+    }
 
-        Ok((form, filename, file_size))
+    /// Sets default options applied to every PDF split call.
+    ///
+    /// Merged field by field with per-call options, which win over the defaults.
+    /// See [`default_rename_options()`](Self::default_rename_options).
+    pub fn default_pdf_split_options(mut self, options: PdfSplitOptions) -> Self {
+        self.default_pdf_split_options = Some(options);
+        self
     }
 
-    /// Creates a multipart form from bytes.
+    /// Sets default options applied to every extract call.
     ///
-    /// Returns the form and file size for logging.
-    fn create_bytes_form(
-        &self,
-        content: Vec<u8>,
-        filename: &str,
-        fields: Vec<(&str, String)>,
-    ) -> Result<(Form, usize)> {
-        let file_size = content.len();
-        let mime_type = mime_guess::from_path(filename)
-            .first_or_octet_stream()
-            .to_string();
+    /// Merged field by field with per-call options, which win over the defaults.
+    /// See [`default_rename_options()`](Self::default_rename_options).
+    pub fn default_extract_options(mut self, options: ExtractOptions) -> Self {
+        self.default_extract_options = Some(options);
+        self
+    }
 
-        let file_part = Part::bytes(content)
-            .file_name(filename.to_string())
-            .mime_str(&mime_type)
-            .map_err(|e| RenamedError::Network {
-                message: format!("Invalid MIME type: {}", e),
-                source: None,
-            })?;
+    /// Builds the [`RenamedClient`].
+    pub fn build(self) -> RenamedClient {
+        let base_url = match &self.api_version {
+            Some(version) => with_api_version(&self.base_url, version),
+            None => self.base_url.clone(),
+        };
 
-        let mut form = Form::new().part("file", file_part);
+        let mut default_headers = reqwest::header::HeaderMap::new();
+        if let Some(language) = &self.accept_language {
+            match reqwest::header::HeaderValue::from_str(language) {
+                Ok(value) => {
+                    default_headers.insert(reqwest::header::ACCEPT_LANGUAGE, value);
+                }
+                Err(_) => warn!(
+                    "[Renamed] Ignoring invalid Accept-Language value: {:?}",
+                    language
+                ),
+            }
+        }
 
-        for (key, value) in fields {
-            form = form.text(key.to_string(), value);
+        let mut http_builder = reqwest::Client::builder()
+            .user_agent(user_agent())
+            .timeout(self.timeout)
+            .default_headers(default_headers);
+        if let Some(connect_timeout) = self.connect_timeout {
+            http_builder = http_builder.connect_timeout(connect_timeout);
+        }
+        if !self.follow_redirects {
+            http_builder = http_builder.redirect(reqwest::redirect::Policy::none());
+        }
+        if let Some(proxy) = self.proxy {
+            http_builder = http_builder.proxy(proxy);
+        }
+        for (domain, addr) in &self.resolve {
+            http_builder = http_builder.resolve(domain, *addr);
         }
 
-        Ok((form, file_size))
-    }
+        let client = http_builder.build().expect("Failed to build HTTP client");
 
-    /// Uploads a file and returns the response body.
-    async fn upload_file(
-        &self,
-        path: &str,
-        file_path: impl AsRef<Path>,
-        fields: Vec<(&str, String)>,
-    ) -> Result<String> {
-        let (form, filename, file_size) = self.create_file_form(file_path, fields).await?;
+        let renamed_client = RenamedClient {
+            api_key: self.api_key,
+            base_url,
+            timeout: self.timeout,
+            deadline: None,
+            retry_strategy: self.retry_strategy.unwrap_or_else(|| {
+                Arc::new(DefaultRetryStrategy::new(
+                    self.max_retries,
+                    self.retry_policy,
+                ))
+            }),
+            circuit_breaker: self
+                .circuit_breaker
+                .map(|(threshold, cooldown)| Arc::new(CircuitBreaker::new(threshold, cooldown))),
+            retry_budget: self
+                .retry_budget
+                .map(|per_second| Arc::new(RetryBudget::new(per_second))),
+            upload_budget: self
+                .max_inflight_bytes
+                .map(|max_bytes| Arc::new(UploadBudget::new(max_bytes))),
+            user_cache: self.user_cache_ttl.map(|ttl| Arc::new(UserCache::new(ttl))),
+            on_complete: self.on_complete,
+            request_id_factory: self.request_id_factory,
+            debug: self.debug,
+            log_bodies: self.log_bodies,
+            mime_detection: self.mime_detection,
+            sandbox: self.sandbox,
+            default_rename_options: self.default_rename_options,
+            default_pdf_split_options: self.default_pdf_split_options,
+            default_extract_options: self.default_extract_options,
+            rate_limit: Arc::new(Mutex::new(None)),
+            maintenance: Arc::new(MaintenanceGate::new()),
+            client: Arc::new(client),
+        };
 
         if self.debug {
-            debug!(
-                "[Renamed] Upload: {} ({})",
-                filename,
-                Self::format_size(file_size)
+            info!(
+                "[Renamed] Client initialized (api_key: {}, base_url: {}{})",
+                renamed_client.mask_api_key(),
+                renamed_client.base_url,
+                if self.sandbox { ", sandbox" } else { "" }
             );
         }
 
-        let url = self.build_url(path);
-        let request = self
-            .request(reqwest::Method::POST, path)
-            .await?
-            .multipart(form);
-        self.execute_request(request, "POST", &url).await
+        renamed_client
     }
 
-    /// Uploads bytes and returns the response body.
-    async fn upload_bytes(
-        &self,
-        path: &str,
-        content: Vec<u8>,
-        filename: &str,
-        fields: Vec<(&str, String)>,
-    ) -> Result<String> {
-        let (form, file_size) = self.create_bytes_form(content, filename, fields)?;
+    /// Builds the [`RenamedClient`] and verifies connectivity and the API key.
+    ///
+    /// Calls [`RenamedClient::get_user()`] once and returns the client only if it
+    /// succeeds, which makes startup fail fast on a bad key or unreachable API.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from the verification request, typically
+    /// [`RenamedError::Authentication`] or [`RenamedError::Network`].
+    pub async fn connect(self) -> Result<RenamedClient> {
+        let client = self.build();
+        client.get_user().await?;
+        Ok(client)
+    }
+}
 
-        if self.debug {
-            debug!(
-                "[Renamed] Upload: {} ({})",
-                filename,
-                Self::format_size(file_size)
-            );
-        }
+/// The main client for interacting with the renamed.to API.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use renamed::RenamedClient;
+///
+/// # async fn example() -> Result<(), renamed::RenamedError> {
+/// let client = RenamedClient::new("rt_your_api_key");
+///
+/// // Get user info
+/// let user = client.get_user().await?;
+/// println!("Credits: {}", user.credits.unwrap_or_default());
+///
+/// // Rename a file
+/// let result = client.rename("invoice.pdf", None).await?;
+/// println!("Suggested: {}", result.suggested_filename);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenamedClient {
+    api_key: String,
+    base_url: String,
+    timeout: Duration,
+    deadline: Option<Instant>,
+    retry_strategy: Arc<dyn RetryStrategy>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    retry_budget: Option<Arc<RetryBudget>>,
+    upload_budget: Option<Arc<UploadBudget>>,
+    user_cache: Option<Arc<UserCache>>,
+    on_complete: Option<CompletionHook>,
+    request_id_factory: Option<RequestIdFactory>,
+    debug: bool,
+    log_bodies: bool,
+    mime_detection: bool,
+    sandbox: bool,
+    default_rename_options: Option<RenameOptions>,
+    default_pdf_split_options: Option<PdfSplitOptions>,
+    default_extract_options: Option<ExtractOptions>,
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    maintenance: Arc<MaintenanceGate>,
+    client: Arc<reqwest::Client>,
+}
 
-        let url = self.build_url(path);
-        let request = self
-            .request(reqwest::Method::POST, path)
-            .await?
-            .multipart(form);
-        self.execute_request(request, "POST", &url).await
+impl RenamedClient {
+    /// Creates a new client with the given API key using default settings.
+    ///
+    /// For custom configuration, use [`RenamedClient::builder()`] instead.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        RenamedClientBuilder::new(api_key).build()
     }
 
-    // ========================================================================
-    // Public API Methods
-    // ========================================================================
-
-    /// Gets the current user's profile and credits.
+    /// Creates a client and verifies connectivity and the API key.
+    ///
+    /// Unlike [`new()`](Self::new), which never touches the network, this makes
+    /// one [`get_user()`](Self::get_user) request and fails if it does. Use
+    /// [`RenamedClientBuilder::connect()`] to verify a custom configuration.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # async fn example() -> Result<(), renamed::RenamedError> {
-    /// # let client = renamed::RenamedClient::new("api_key");
-    /// let user = client.get_user().await?;
-    /// println!("Email: {}", user.email);
-    /// println!("Credits: {}", user.credits.unwrap_or(0));
+    /// let client = renamed::RenamedClient::connect("rt_your_api_key").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_user(&self) -> Result<User> {
-        let path = "/user";
-        let url = self.build_url(path);
-        let request = self.request(reqwest::Method::GET, path).await?;
-        let body = self.execute_request(request, "GET", &url).await?;
-        serde_json::from_str(&body).map_err(RenamedError::from_serde)
+    pub async fn connect(api_key: impl Into<String>) -> Result<Self> {
+        RenamedClientBuilder::new(api_key).connect().await
     }
 
-    /// Renames a file using AI.
+    /// Creates a client in sandbox (test) mode.
     ///
-    /// Analyzes the file content and suggests an appropriate filename.
+    /// Equivalent to `RenamedClient::builder(api_key).sandbox(true).build()`.
+    /// Sandbox mode is currently a no-op and requests still consume credits;
+    /// see [`RenamedClientBuilder::sandbox()`] for testing against a local mock.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `file` - Path to the file to rename.
-    /// * `options` - Optional configuration for the rename operation.
+    /// ```rust
+    /// let client = renamed::RenamedClient::sandbox("rt_your_api_key");
+    /// assert!(client.is_sandbox());
+    /// ```
+    pub fn sandbox(api_key: impl Into<String>) -> Self {
+        RenamedClientBuilder::new(api_key).sandbox(true).build()
+    }
+
+    /// Creates a builder for configuring the client.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use renamed::{RenamedClient, RenameOptions};
-    ///
-    /// # async fn example() -> Result<(), renamed::RenamedError> {
-    /// let client = RenamedClient::new("rt_your_api_key");
-    ///
-    /// // Basic usage
-    /// let result = client.rename("document.pdf", None).await?;
-    /// println!("Suggested: {}", result.suggested_filename);
+    /// use renamed::RenamedClient;
+    /// use std::time::Duration;
     ///
-    /// // With custom template
-    /// let options = RenameOptions::new().with_template("{date}_{type}_{vendor}");
-    /// let result = client.rename("invoice.pdf", Some(options)).await?;
-    /// # Ok(())
-    /// # }
+    /// let client = RenamedClient::builder("rt_your_api_key")
+    ///     .timeout(Duration::from_secs(60))
+    ///     .max_retries(3)
+    ///     .build();
     /// ```
-    pub async fn rename(
-        &self,
-        file: impl AsRef<Path>,
-        options: Option<RenameOptions>,
-    ) -> Result<RenameResult> {
-        let mut fields = Vec::new();
+    pub fn builder(api_key: impl Into<String>) -> RenamedClientBuilder {
+        RenamedClientBuilder::new(api_key)
+    }
 
-        if let Some(opts) = options {
-            if let Some(template) = opts.template {
-                fields.push(("template", template));
-            }
+    /// Builds the full URL for an API endpoint.
+    fn build_url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return path.to_string();
         }
-
-        let body = self.upload_file("/rename", file, fields).await?;
-        serde_json::from_str(&body).map_err(RenamedError::from_serde)
+        let path = path.trim_start_matches('/');
+        format!("{}/{}", self.base_url, path)
     }
 
-    /// Renames a file from bytes.
-    ///
-    /// Same as [`rename()`](Self::rename) but accepts raw bytes instead of a file path.
-    ///
-    /// # Arguments
+    /// Masks the API key for safe logging.
     ///
-    /// * `content` - The file content as bytes.
-    /// * `filename` - The filename to use (for MIME type detection).
-    /// * `options` - Optional configuration for the rename operation.
-    pub async fn rename_bytes(
-        &self,
-        content: Vec<u8>,
-        filename: &str,
-        options: Option<RenameOptions>,
-    ) -> Result<RenameResult> {
-        let mut fields = Vec::new();
+    /// Returns format like `rt_...xxxx` (first 3 chars + last 4).
+    fn mask_api_key(&self) -> String {
+        let key = &self.api_key;
+        if key.len() <= 7 {
+            return "***".to_string();
+        }
+        let prefix = &key[..3];
+        let suffix = &key[key.len() - 4..];
+        format!("{}...{}", prefix, suffix)
+    }
 
-        if let Some(opts) = options {
-            if let Some(template) = opts.template {
-                fields.push(("template", template));
+    /// Formats a file size in human-readable format.
+    fn format_size(bytes: usize) -> String {
+        const KB: usize = 1024;
+        const MB: usize = KB * 1024;
+        const GB: usize = MB * 1024;
+
+        if bytes >= GB {
+            format!("{:.1} GB", bytes as f64 / GB as f64)
+        } else if bytes >= MB {
+            format!("{:.1} MB", bytes as f64 / MB as f64)
+        } else if bytes >= KB {
+            format!("{:.1} KB", bytes as f64 / KB as f64)
+        } else {
+            format!("{} B", bytes)
+        }
+    }
+
+    /// Extracts the path from a URL for logging.
+    fn extract_path(url: &str) -> &str {
+        // For full URLs, extract the path portion
+        if let Some(idx) = url.find("://") {
+            let after_scheme = &url[idx + 3..];
+            if let Some(path_idx) = after_scheme.find('/') {
+                return &after_scheme[path_idx..];
             }
         }
+        // For relative paths, return as-is
+        url
+    }
 
-        let body = self
-            .upload_bytes("/rename", content, filename, fields)
-            .await?;
-        serde_json::from_str(&body).map_err(RenamedError::from_serde)
+    /// Returns whether debug logging is enabled.
+    pub fn is_debug_enabled(&self) -> bool {
+        self.debug
     }
 
-    /// Splits a PDF into multiple documents.
-    ///
-    /// Returns an [`AsyncJob`] that can be polled for completion. PDF splitting
-    /// is an asynchronous operation that may take some time for large documents.
-    ///
-    /// # Arguments
+    /// Returns the rate-limit budget reported by the most recent API response.
     ///
-    /// * `file` - Path to the PDF file to split.
-    /// * `options` - Optional configuration for the split operation.
+    /// Updated from the `X-RateLimit-*` headers of every response, successful
+    /// or not, and shared between clones of this client. Returns `None` until
+    /// a response carrying those headers has been received.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use renamed::{RenamedClient, PdfSplitOptions, SplitMode};
-    ///
     /// # async fn example() -> Result<(), renamed::RenamedError> {
-    /// let client = RenamedClient::new("rt_your_api_key");
-    ///
-    /// // Auto-detect document boundaries
-    /// let job = client.pdf_split("multi-page.pdf", None).await?;
-    /// let result = job.wait(None).await?;
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// client.rename("invoice.pdf", None).await?;
     ///
-    /// for doc in result.documents {
-    ///     println!("{}: pages {}", doc.filename, doc.pages);
+    /// if let Some(info) = client.last_rate_limit() {
+    ///     if info.remaining == Some(0) {
+    ///         println!("Out of requests until {:?}", info.reset_at);
+    ///     }
     /// }
-    ///
-    /// // Split every 5 pages
-    /// let options = PdfSplitOptions::new()
-    ///     .with_mode(SplitMode::Pages)
-    ///     .with_pages_per_split(5);
-    /// let job = client.pdf_split("large.pdf", Some(options)).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn pdf_split(
-        &self,
-        file: impl AsRef<Path>,
-        options: Option<PdfSplitOptions>,
-    ) -> Result<AsyncJob> {
-        let mut fields = Vec::new();
-
-        if let Some(opts) = options {
-            if let Some(mode) = opts.mode {
-                fields.push(("mode", mode.to_string()));
-            }
-            if let Some(pages) = opts.pages_per_split {
-                fields.push(("pagesPerSplit", pages.to_string()));
-            }
-        }
-
-        let body = self.upload_file("/pdf-split", file, fields).await?;
-        let response: PdfSplitResponse =
-            serde_json::from_str(&body).map_err(RenamedError::from_serde)?;
-
-        Ok(AsyncJob::new(
-            Arc::clone(&self.client),
-            self.api_key.clone(),
-            response.status_url,
-            self.debug,
-        ))
+    pub fn last_rate_limit(&self) -> Option<RateLimitInfo> {
+        *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner())
     }
 
-    /// Splits a PDF from bytes.
-    ///
-    /// Same as [`pdf_split()`](Self::pdf_split) but accepts raw bytes.
-    pub async fn pdf_split_bytes(
-        &self,
-        content: Vec<u8>,
-        filename: &str,
-        options: Option<PdfSplitOptions>,
-    ) -> Result<AsyncJob> {
-        let mut fields = Vec::new();
-
-        if let Some(opts) = options {
-            if let Some(mode) = opts.mode {
-                fields.push(("mode", mode.to_string()));
-            }
-            if let Some(pages) = opts.pages_per_split {
-                fields.push(("pagesPerSplit", pages.to_string()));
-            }
-        }
-
-        let body = self
-            .upload_bytes("/pdf-split", content, filename, fields)
-            .await?;
-        let response: PdfSplitResponse =
-            serde_json::from_str(&body).map_err(RenamedError::from_serde)?;
-
-        Ok(AsyncJob::new(
-            Arc::clone(&self.client),
-            self.api_key.clone(),
-            response.status_url,
-            self.debug,
-        ))
+    /// Returns whether the client is in sandbox (test) mode.
+    pub fn is_sandbox(&self) -> bool {
+        self.sandbox
     }
 
-    /// Extracts structured data from a document.
-    ///
-    /// Uses AI to extract data matching a schema or natural language prompt.
-    ///
-    /// # Arguments
+    /// Returns a handle to this client whose requests must finish by `deadline`.
     ///
-    /// * `file` - Path to the document to extract data from.
-    /// * `options` - Configuration specifying what to extract.
+    /// Each request through the returned client is given a timeout of
+    /// `min(time left until deadline, client timeout)`, and retries that would
+    /// wait past the deadline are skipped. Once the deadline has passed,
+    /// requests fail immediately with [`RenamedError::Timeout`]. Jobs started
+    /// through the returned client stop polling at the deadline too. Use it to
+    /// propagate a caller's deadline (e.g. from a gRPC context) into SDK calls.
+    /// The handle shares this client's connection pool and is cheap to create.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use renamed::{RenamedClient, ExtractOptions};
-    ///
     /// # async fn example() -> Result<(), renamed::RenamedError> {
-    /// let client = RenamedClient::new("rt_your_api_key");
+    /// use std::time::{Duration, Instant};
     ///
-    /// // Using natural language prompt
-    /// let options = ExtractOptions::new()
-    ///     .with_prompt("Extract invoice number, date, and total amount");
-    /// let result = client.extract("invoice.pdf", Some(options)).await?;
-    ///
-    /// println!("Extracted data: {:?}", result.data);
-    /// println!("Confidence: {:.0}%", result.confidence * 100.0);
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// let result = client.with_deadline(deadline).rename("invoice.pdf", None).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn extract(
-        &self,
-        file: impl AsRef<Path>,
-        options: Option<ExtractOptions>,
-    ) -> Result<ExtractResult> {
-        let mut fields = Vec::new();
-
-        if let Some(opts) = options {
-            if let Some(prompt) = opts.prompt {
-                fields.push(("prompt", prompt));
-            }
-            if let Some(schema) = opts.schema {
-                let schema_json =
-                    serde_json::to_string(&schema).map_err(RenamedError::from_serde)?;
-                fields.push(("schema", schema_json));
-            }
+    pub fn with_deadline(&self, deadline: Instant) -> RenamedClient {
+        RenamedClient {
+            deadline: Some(deadline),
+            ..self.clone()
         }
+    }
 
-        let body = self.upload_file("/extract", file, fields).await?;
-        serde_json::from_str(&body).map_err(RenamedError::from_serde)
+    /// Returns the deadline set by [`with_deadline()`](Self::with_deadline), if any.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
     }
 
-    /// Extracts data from bytes.
-    ///
-    /// Same as [`extract()`](Self::extract) but accepts raw bytes.
-    pub async fn extract_bytes(
-        &self,
-        content: Vec<u8>,
-        filename: &str,
-        options: Option<ExtractOptions>,
-    ) -> Result<ExtractResult> {
-        let mut fields = Vec::new();
+    /// Caps the request's timeout at the time left until the deadline.
+    fn apply_deadline(&self, request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        let Some(deadline) = self.deadline else {
+            return Ok(request);
+        };
 
-        if let Some(opts) = options {
-            if let Some(prompt) = opts.prompt {
-                fields.push(("prompt", prompt));
-            }
-            if let Some(schema) = opts.schema {
-                let schema_json =
-                    serde_json::to_string(&schema).map_err(RenamedError::from_serde)?;
-                fields.push(("schema", schema_json));
-            }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(RenamedError::deadline_exceeded());
         }
-
-        let body = self
-            .upload_bytes("/extract", content, filename, fields)
-            .await?;
-        serde_json::from_str(&body).map_err(RenamedError::from_serde)
+        Ok(request.timeout(remaining.min(self.timeout)))
     }
 
-    /// Downloads a file from a URL (e.g., a split document).
-    ///
-    /// # Arguments
+    /// Returns the underlying HTTP client.
     ///
-    /// * `url` - The URL to download from.
+    /// This is an escape hatch for endpoints the SDK doesn't model. The
+    /// returned client shares this client's connection pool, timeouts, proxy,
+    /// and default headers, but requests made with it do not include the
+    /// `Authorization` header, retries, or debug logging.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = renamed::RenamedClient::new("api_key");
-    /// let job = client.pdf_split("document.pdf", None).await?;
-    /// let result = job.wait(None).await?;
-    ///
-    /// for doc in result.documents {
-    ///     let content = client.download_file(&doc.download_url).await?;
-    ///     tokio::fs::write(&doc.filename, content).await?;
-    /// }
+    /// # async fn example() -> Result<(), reqwest::Error> {
+    /// # let client = renamed::RenamedClient::new("rt_your_api_key");
+    /// let response = client
+    ///     .http_client()
+    ///     .get("https://www.renamed.to/api/v1/events")
+    ///     .bearer_auth("rt_your_api_key")
+    ///     .send()
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn download_file(&self, url: &str) -> Result<Vec<u8>> {
-        let start = Instant::now();
-
-        let response = self
-            .client
-            .get(url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await
-            .map_err(RenamedError::from_reqwest)?;
-
-        let status_code = response.status().as_u16();
-        let elapsed_ms = start.elapsed().as_millis();
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.client
+    }
 
+    /// Closes the client and releases its connection pool.
+    ///
+    /// The underlying HTTP connection pool is shared by every clone of this
+    /// client and by the [`AsyncJob`]s it created; idle connections are closed
+    /// when the last of these handles is dropped. reqwest does not expose
+    /// explicit draining, so this is equivalent to dropping the client, but makes
+    /// teardown explicit in shutdown paths.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this was the last handle and the pool was released, `false` if
+    /// clones or jobs still hold it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// let client = renamed::RenamedClient::new("rt_your_api_key");
+    /// // ... use the client ...
+    /// if !client.close() {
+    ///     eprintln!("Connection pool still in use by other handles");
+    /// }
+    /// ```
+    pub fn close(self) -> bool {
+        let released = Arc::strong_count(&self.client) == 1;
         if self.debug {
             debug!(
-                "[Renamed] GET {} -> {} ({}ms)",
-                Self::extract_path(url),
-                status_code,
-                elapsed_ms
+                "[Renamed] Client closed ({})",
+                if released {
+                    "connection pool released"
+                } else {
+                    "connection pool still shared"
+                }
             );
         }
-
-        if status_code >= 400 {
-            let body = response.text().await.map_err(RenamedError::from_reqwest)?;
-            return Err(RenamedError::from_http_status(status_code, Some(&body)));
-        }
-
-        response
-            .bytes()
-            .await
-            .map(|b| b.to_vec())
-            .map_err(RenamedError::from_reqwest)
+        released
     }
-}
 
-#[cfg(test)]
+    /// Makes an HTTP request with retry logic.
+    ///
+    /// Every authenticated request, including downloads and job status polls,
+    /// is built here. Fails with [`RenamedError::Authentication`] if the API
+    /// key can't be sent in a header.
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder> {
+        let (name, value) = auth_header(&self.api_key)?;
+        Ok(self
+            .unauthenticated_request(method, path)
+            .header(name, value))
+    }
+
+    /// Builds a request without credentials, carrying a request id if configured.
+    fn unauthenticated_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> reqwest::RequestBuilder {
+        let url = self.build_url(path);
+        let mut request = self.client.request(method.clone(), url);
+
+        if let Some(factory) = &self.request_id_factory {
+            let request_id = (factory.0)();
+            if self.debug {
+                debug!(
+                    "[Renamed] {} {} request id: {}",
+                    method,
+                    Self::extract_path(path),
+                    request_id
+                );
+            }
+            request = request.header(REQUEST_ID_HEADER, request_id);
+        }
+
+        request
+    }
+
+    /// Fetches a job's status or result payload from its absolute `url`.
+    ///
+    /// Goes through the same retry loop as API calls. The API key is only
+    /// sent when `authenticate` is set, so pre-signed storage URLs never
+    /// receive it.
+    pub(crate) async fn get_job_resource(
+        &self,
+        url: &str,
+        query: &[(&str, u64)],
+        authenticate: bool,
+    ) -> Result<String> {
+        let mut request = if authenticate {
+            self.request(reqwest::Method::GET, url).await?
+        } else {
+            self.unauthenticated_request(reqwest::Method::GET, url)
+        };
+        if !query.is_empty() {
+            request = request.query(query);
+        }
+        self.execute_request(request, "GET", url).await
+    }
+
+    /// Executes a request with retry logic and returns the response body.
+    async fn execute_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        method: &str,
+        path: &str,
+    ) -> Result<String> {
+        self.execute_with(
+            || std::future::ready(Self::clone_request(&request)),
+            method,
+            path,
+        )
+        .await
+    }
+
+    /// Executes a request with retry logic, building a fresh request for each
+    /// attempt with `make_request`.
+    ///
+    /// Requests with streaming bodies (e.g. multipart uploads) can't be cloned,
+    /// so they are rebuilt from their source instead.
+    async fn execute_with<Fut>(
+        &self,
+        make_request: impl Fn() -> Fut,
+        method: &str,
+        path: &str,
+    ) -> Result<String>
+    where
+        Fut: Future<Output = Result<reqwest::RequestBuilder>>,
+    {
+        let start = Instant::now();
+        let mut attempts = Attempts::default();
+
+        let result = self
+            .send_with_retries(make_request, method, path, start, &mut attempts)
+            .await
+            .map_err(|e| e.with_elapsed(start.elapsed()));
+
+        self.report_completion(method, path, start, &attempts, result.is_ok());
+        result
+    }
+
+    /// Passes the metrics of a finished request to the completion hook, if any.
+    fn report_completion(
+        &self,
+        method: &str,
+        path: &str,
+        start: Instant,
+        attempts: &Attempts,
+        success: bool,
+    ) {
+        if let Some(hook) = &self.on_complete {
+            hook.call(&RequestMetrics {
+                method: method.to_string(),
+                url: path.to_string(),
+                status_code: attempts.status_code,
+                retries: attempts.retries,
+                elapsed: start.elapsed(),
+                success,
+            });
+        }
+    }
+
+    /// Fails fast while a maintenance window is announced or the circuit is open.
+    fn check_gates(&self, method: &str, path: &str) -> Result<()> {
+        if let Some(wait) = self.maintenance.remaining() {
+            if self.debug {
+                warn!(
+                    "[Renamed] API unavailable for {}s, failing fast: {} {}",
+                    wait.as_secs(),
+                    method,
+                    Self::extract_path(path)
+                );
+            }
+            return Err(RenamedError::service_unavailable(wait));
+        }
+
+        if let Some(breaker) = &self.circuit_breaker {
+            if !breaker.allow_request() {
+                if self.debug {
+                    warn!(
+                        "[Renamed] Circuit open, failing fast: {} {}",
+                        method,
+                        Self::extract_path(path)
+                    );
+                }
+                return Err(breaker.open_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns how long to wait before retrying after `error`, or `None` if the
+    /// request must not be retried.
+    ///
+    /// Asks the retry strategy first, then gives up if the wait would end past
+    /// the deadline or the retry budget is exhausted.
+    fn retry_delay(
+        &self,
+        retries: u32,
+        error: &RenamedError,
+        method: &str,
+        path: &str,
+    ) -> Option<Duration> {
+        let delay = self.retry_strategy.next_delay(retries, error)?;
+        if self.deadline.is_some_and(|deadline| {
+            Instant::now()
+                .checked_add(delay)
+                .is_none_or(|resume| resume >= deadline)
+        }) {
+            return None;
+        }
+        if let Some(budget) = &self.retry_budget {
+            if !budget.try_acquire() {
+                if self.debug {
+                    warn!(
+                        "[Renamed] Retry budget exhausted, not retrying: {} {}",
+                        method,
+                        Self::extract_path(path)
+                    );
+                }
+                return None;
+            }
+        }
+        Some(delay)
+    }
+
+    /// Runs the retry loop, recording retries and the last status in `attempts`.
+    async fn send_with_retries<Fut>(
+        &self,
+        make_request: impl Fn() -> Fut,
+        method: &str,
+        path: &str,
+        start: Instant,
+        attempts: &mut Attempts,
+    ) -> Result<String>
+    where
+        Fut: Future<Output = Result<reqwest::RequestBuilder>>,
+    {
+        loop {
+            self.check_gates(method, path)?;
+
+            let req = self.apply_deadline(make_request().await?)?;
+
+            let error = match req.send().await {
+                Ok(response) => {
+                    let status_code = response.status().as_u16();
+                    attempts.status_code = Some(status_code);
+                    if let Some(info) =
+                        RateLimitInfo::from_headers(response.headers(), SystemTime::now())
+                    {
+                        *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner()) = Some(info);
+                    }
+                    let maintenance = (status_code == 503)
+                        .then(|| response.headers().get(reqwest::header::RETRY_AFTER))
+                        .flatten()
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| parse_retry_after(value, SystemTime::now()))
+                        .filter(|wait| !wait.is_zero());
+                    let elapsed_ms = start.elapsed().as_millis();
+                    let body = match response.text().await {
+                        Ok(body) => body,
+                        Err(err) => {
+                            let error = RenamedError::from_reqwest(err);
+                            if let Some(breaker) = &self.circuit_breaker {
+                                breaker.record(Err(&error));
+                            }
+                            return Err(error);
+                        }
+                    };
+
+                    if self.debug {
+                        debug!(
+                            "[Renamed] {} {} -> {} ({}ms)",
+                            method,
+                            Self::extract_path(path),
+                            status_code,
+                            elapsed_ms
+                        );
+                        if self.log_bodies {
+                            debug!("[Renamed] Response body: {}", body);
+                        }
+                    }
+
+                    if let Some(wait) = maintenance {
+                        // Every clone of the client waits out the announced window
+                        self.maintenance.close_for(wait);
+                        RenamedError::service_unavailable(wait)
+                    } else if status_code >= 300 {
+                        // A 3xx only gets here when it wasn't followed, so it's an error
+                        RenamedError::from_http_status(status_code, Some(&body))
+                    } else if let Some(error) = RenamedError::from_soft_error(&body) {
+                        // An error payload behind a success status, e.g. from a proxy
+                        error
+                    } else {
+                        if let Some(breaker) = &self.circuit_breaker {
+                            breaker.record(Ok(()));
+                        }
+                        return Ok(body);
+                    }
+                }
+                Err(err) => {
+                    attempts.status_code = None;
+                    RenamedError::from_reqwest(err)
+                }
+            };
+
+            if let Some(breaker) = &self.circuit_breaker {
+                breaker.record(Err(&error));
+            }
+
+            let Some(delay) = self.retry_delay(attempts.retries, &error, method, path) else {
+                return Err(error);
+            };
+
+            attempts.retries += 1;
+            if self.debug {
+                warn!(
+                    "[Renamed] Retry attempt {}, waiting {}ms",
+                    attempts.retries,
+                    delay.as_millis()
+                );
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Clones a request whose body is in memory, for another attempt.
+    fn clone_request(request: &reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        request.try_clone().ok_or_else(|| RenamedError::Network {
+            message: "Failed to clone request for retry".into(),
+            source: None,
+            elapsed: None,
+        })
+    }
+
+    /// Creates a multipart form from the file part and form fields.
+    fn upload_form(
+        file_part: Part,
+        filename: &str,
+        mime_type: &str,
+        fields: &[(&str, String)],
+    ) -> Result<Form> {
+        let file_part = file_part
+            .file_name(filename.to_string())
+            .mime_str(mime_type)
+            .map_err(|e| RenamedError::Network {
+                message: format!("Invalid MIME type: {}", e).into(),
+                source: None,
+                elapsed: None,
+            })?;
+
+        let mut form = Form::new().part("file", file_part);
+
+        for (key, value) in fields {
+            form = form.text(key.to_string(), value.clone());
+        }
+
+        Ok(form)
+    }
+
+    /// Returns the content type to upload `path` with.
+    fn mime_type_for(&self, path: &Path) -> String {
+        if !self.mime_detection {
+            return "application/octet-stream".to_string();
+        }
+        mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .to_string()
+    }
+
+    /// Returns the filename sent with an upload of `path`.
+    fn upload_filename(path: &Path) -> String {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file")
+            .to_string()
+    }
+
+    /// Describes multipart form fields for debug logging, redacting the file.
+    fn describe_fields(fields: &[(&str, String)]) -> String {
+        std::iter::once("file=<redacted>".to_string())
+            .chain(
+                fields
+                    .iter()
+                    .map(|(key, value)| format!("{}={:?}", key, value)),
+            )
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Uploads a file and returns the response body.
+    async fn upload_file(
+        &self,
+        path: &str,
+        file_path: impl AsRef<Path>,
+        fields: Vec<(&str, String)>,
+    ) -> Result<String> {
+        let file_path = file_path.as_ref();
+        let read_error =
+            |e| RenamedError::from_io(e, format!("Failed to read file: {}", file_path.display()));
+
+        // Reserve the budget before reading so waiting uploads hold no memory
+        let _permit = match &self.upload_budget {
+            Some(budget) => {
+                let len = tokio::fs::metadata(file_path)
+                    .await
+                    .map_err(read_error)?
+                    .len();
+                Some(budget.acquire(len).await)
+            }
+            None => None,
+        };
+
+        let content = tokio::fs::read(file_path).await.map_err(read_error)?;
+        let filename = Self::upload_filename(file_path);
+        let mime_type = self.mime_type_for(file_path);
+
+        self.send_bytes(path, content, &filename, &mime_type, fields)
+            .await
+    }
+
+    /// Uploads bytes with the given content type and returns the response body.
+    async fn upload_bytes(
+        &self,
+        path: &str,
+        content: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        fields: Vec<(&str, String)>,
+    ) -> Result<String> {
+        let _permit = match &self.upload_budget {
+            Some(budget) => Some(budget.acquire(content.len() as u64).await),
+            None => None,
+        };
+
+        self.send_bytes(path, content, filename, mime_type, fields)
+            .await
+    }
+
+    /// Sends buffered upload content without reserving upload budget.
+    ///
+    /// The content is shared between attempts rather than copied for each.
+    async fn send_bytes(
+        &self,
+        path: &str,
+        content: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        fields: Vec<(&str, String)>,
+    ) -> Result<String> {
+        let len = content.len() as u64;
+        self.log_upload(filename, len, &fields);
+        let content = Bytes::from(content);
+
+        self.send_multipart(path, || {
+            let body = reqwest::Body::from(content.clone());
+            std::future::ready(Self::upload_form(
+                Part::stream_with_length(body, len),
+                filename,
+                mime_type,
+                &fields,
+            ))
+        })
+        .await
+    }
+
+    /// Uploads the content of a reader and returns the response body.
+    ///
+    /// `make_reader` is called once per attempt, so retries re-read the source
+    /// from the start instead of buffering it in memory.
+    async fn upload_reader<F, Fut, R>(
+        &self,
+        path: &str,
+        make_reader: F,
+        filename: &str,
+        len: u64,
+        fields: Vec<(&str, String)>,
+    ) -> Result<String>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::io::Result<R>>,
+        R: AsyncRead + Send + 'static,
+    {
+        self.log_upload(filename, len, &fields);
+        let mime_type = &self.mime_type_for(Path::new(filename));
+        let make_reader = &make_reader;
+        let fields = &fields;
+
+        self.send_multipart(path, move || async move {
+            let reader = make_reader()
+                .await
+                .map_err(|e| RenamedError::from_io(e, format!("Failed to open {}", filename)))?;
+            let body = reqwest::Body::wrap_stream(reader_stream(reader));
+            Self::upload_form(
+                Part::stream_with_length(body, len),
+                filename,
+                mime_type,
+                fields,
+            )
+        })
+        .await
+    }
+
+    /// Logs an upload and its form fields when debug logging is enabled.
+    fn log_upload(&self, filename: &str, file_size: u64, fields: &[(&str, String)]) {
+        if self.debug {
+            debug!(
+                "[Renamed] Upload: {} ({})",
+                filename,
+                Self::format_size(file_size as usize)
+            );
+            if self.log_bodies {
+                debug!("[Renamed] Form fields: {}", Self::describe_fields(fields));
+            }
+        }
+    }
+
+    /// Sends a multipart POST, building the form anew for each attempt.
+    async fn send_multipart<Fut>(&self, path: &str, make_form: impl Fn() -> Fut) -> Result<String>
+    where
+        Fut: Future<Output = Result<Form>>,
+    {
+        let url = self.build_url(path);
+        let request = &self.request(reqwest::Method::POST, path).await?;
+        let make_form = &make_form;
+        let result = self
+            .execute_with(
+                move || async move { Ok(Self::clone_request(request)?.multipart(make_form().await?)) },
+                "POST",
+                &url,
+            )
+            .await;
+
+        // Uploads may spend credits, so the cached balance is stale
+        if let Some(cache) = &self.user_cache {
+            cache.invalidate();
+        }
+        result
+    }
+
+    /// Asks the API to fetch `source_url` itself and returns the response body.
+    ///
+    /// Sends the URL under `url_field` together with the request fields as a
+    /// JSON body, instead of uploading the file as multipart.
+    async fn fetch_remote(
+        &self,
+        path: &str,
+        url_field: &str,
+        source_url: &str,
+        fields: Vec<(&str, String)>,
+    ) -> Result<String> {
+        if self.debug {
+            // Query strings of pre-signed URLs carry credentials, so leave them out
+            let shown = source_url.split('?').next().unwrap_or_default();
+            debug!("[Renamed] Remote fetch: {}", shown);
+            if self.log_bodies {
+                debug!(
+                    "[Renamed] Request fields: {}",
+                    Self::describe_fields(&fields)
+                );
+            }
+        }
+
+        let mut json = serde_json::Map::new();
+        json.insert(url_field.to_string(), source_url.into());
+        for (key, value) in fields {
+            json.insert(key.to_string(), Self::json_field_value(key, value));
+        }
+
+        let url = self.build_url(path);
+        let request = self.request(reqwest::Method::POST, path).await?.json(&json);
+        let result = self.execute_request(request, "POST", &url).await;
+
+        // Remote fetches spend credits like uploads, so the cached balance is stale
+        if let Some(cache) = &self.user_cache {
+            cache.invalidate();
+        }
+        result
+    }
+
+    /// Converts a form field to its JSON type for [`fetch_remote()`](Self::fetch_remote).
+    fn json_field_value(key: &str, value: String) -> serde_json::Value {
+        let typed = match key {
+            "pagesPerSplit" => value.parse::<u32>().ok().map(Into::into),
+            "inlineOutput" | "includeMetadata" => value.parse::<bool>().ok().map(Into::into),
+            _ => None,
+        };
+        typed.unwrap_or_else(|| value.into())
+    }
+
+    /// Returns the last path segment of a URL, used as the remote file's name.
+    fn remote_filename(source_url: &str) -> String {
+        let path = source_url.split(['?', '#']).next().unwrap_or_default();
+        match path.rsplit_once('/') {
+            Some((rest, name)) if !name.is_empty() && !rest.ends_with('/') => name.to_string(),
+            _ => "file".to_string(),
+        }
+    }
+
+    /// Builds the form fields for a rename request, applying client defaults.
+    fn rename_fields(&self, options: Option<RenameOptions>, filename: &str) -> FormFields {
+        let mut fields = Vec::new();
+
+        if let Some(opts) = merge_options(options, self.default_rename_options.as_ref()) {
+            if let Some(template) = opts.template {
+                fields.push(("template", template));
+            }
+            if opts.original_name_as_hint == Some(true) {
+                fields.push(("hint", filename.to_string()));
+            }
+            if opts.include_metadata == Some(true) {
+                fields.push(("includeMetadata", "true".to_string()));
+            }
+        }
+
+        fields
+    }
+
+    /// Returns the filename format to apply locally, applying client defaults.
+    ///
+    /// `None` when neither a case nor a separator is set.
+    fn rename_format(&self, options: Option<&RenameOptions>) -> Option<(Case, Option<char>)> {
+        let defaults = self.default_rename_options.as_ref();
+        let case = options
+            .and_then(|o| o.case)
+            .or(defaults.and_then(|d| d.case));
+        let separator = options
+            .and_then(|o| o.separator)
+            .or(defaults.and_then(|d| d.separator));
+
+        (case.is_some() || separator.is_some()).then(|| (case.unwrap_or_default(), separator))
+    }
+
+    /// Parses a rename response, reformatting the suggestion if requested.
+    fn parse_rename(body: &str, format: Option<(Case, Option<char>)>) -> Result<RenameResult> {
+        let mut result: RenameResult =
+            serde_json::from_str(body).map_err(RenamedError::from_serde)?;
+        if let Some((case, separator)) = format {
+            result.suggested_filename = result.formatted(case, separator);
+        }
+        Ok(result)
+    }
+
+    /// Applies client defaults to split options and builds the request's
+    /// form fields.
+    ///
+    /// Returns the merged options too, so the job can be configured from them.
+    fn pdf_split_fields(
+        &self,
+        options: Option<PdfSplitOptions>,
+    ) -> (Option<PdfSplitOptions>, FormFields) {
+        let options = merge_options(options, self.default_pdf_split_options.as_ref());
+        let mut fields = Vec::new();
+
+        if let Some(opts) = &options {
+            if let Some(mode) = opts.mode {
+                fields.push(("mode", mode.to_string()));
+            }
+            if let Some(pages) = opts.pages_per_split {
+                fields.push(("pagesPerSplit", pages.to_string()));
+            }
+            if opts.inline_output == Some(true) {
+                fields.push(("inlineOutput", "true".to_string()));
+            }
+        }
+
+        (options, fields)
+    }
+
+    /// Applies client defaults to extract options and builds the request's
+    /// form fields.
+    ///
+    /// Returns the merged options too, so the response can be validated
+    /// against them.
+    fn extract_fields(
+        &self,
+        options: Option<ExtractOptions>,
+    ) -> Result<(Option<ExtractOptions>, FormFields)> {
+        let options = merge_options(options, self.default_extract_options.as_ref());
+        let mut fields = Vec::new();
+
+        if let Some(opts) = &options {
+            if let Some(prompt) = &opts.prompt {
+                fields.push(("prompt", prompt.clone()));
+            }
+            if let Some(schema) = &opts.schema {
+                let schema_json =
+                    serde_json::to_string(schema).map_err(RenamedError::from_serde)?;
+                fields.push(("schema", schema_json));
+            }
+            if let Some(language) = &opts.output_language {
+                fields.push(("outputLanguage", language.clone()));
+            }
+            if let Some(context) = &opts.context {
+                fields.push(("context", context.clone()));
+            }
+        }
+
+        Ok((options, fields))
+    }
+
+    /// Parses an extract response, validating it against the schema if
+    /// `options` enable validation.
+    #[cfg_attr(not(feature = "jsonschema"), allow(unused_variables))]
+    fn parse_extract_result(body: &str, options: Option<&ExtractOptions>) -> Result<ExtractResult> {
+        let result: ExtractResult = serde_json::from_str(body).map_err(RenamedError::from_serde)?;
+
+        #[cfg(feature = "jsonschema")]
+        if let Some(schema) = options
+            .filter(|opts| opts.validate == Some(true))
+            .and_then(|opts| opts.schema.as_ref())
+        {
+            result.validate_against(schema)?;
+        }
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Public API Methods
+    // ========================================================================
+
+    /// Gets the current user's profile and credits.
+    ///
+    /// Returns a cached profile if [`RenamedClientBuilder::cache_user()`] is
+    /// enabled and the cache is still fresh.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let user = client.get_user().await?;
+    /// println!("Email: {}", user.email);
+    /// println!("Credits: {}", user.credits.unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_user(&self) -> Result<User> {
+        if let Some(user) = self.user_cache.as_ref().and_then(|cache| cache.get()) {
+            return Ok(user);
+        }
+        self.get_user_fresh().await
+    }
+
+    /// Gets the current user's profile, bypassing and refreshing the cache.
+    ///
+    /// See [`RenamedClientBuilder::cache_user()`].
+    pub async fn get_user_fresh(&self) -> Result<User> {
+        let path = "/user";
+        let url = self.build_url(path);
+        let request = self.request(reqwest::Method::GET, path).await?;
+        let body = self.execute_request(request, "GET", &url).await?;
+        let user: User = serde_json::from_str(&body).map_err(RenamedError::from_serde)?;
+
+        if let Some(cache) = &self.user_cache {
+            cache.set(user.clone());
+        }
+        Ok(user)
+    }
+
+    /// Checks the connection to the API and returns the round-trip time.
+    ///
+    /// Sends the lightweight, non-billable user profile request, since the
+    /// API has no dedicated health endpoint. The time includes any retries.
+    /// Useful for diagnostics: compare it with the time an operation takes to
+    /// tell network latency from processing time.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let latency = client.ping().await?;
+    /// println!("API reachable in {}ms", latency.as_millis());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&self) -> Result<Duration> {
+        let start = Instant::now();
+        let path = "/user";
+        let url = self.build_url(path);
+        let request = self.request(reqwest::Method::GET, path).await?;
+        self.execute_request(request, "GET", &url).await?;
+        Ok(start.elapsed())
+    }
+
+    /// Runs `operation` and reports how many credits it consumed.
+    ///
+    /// Reads the credit balance with [`get_user()`](Self::get_user) before the
+    /// operation and with [`get_user_fresh()`](Self::get_user_fresh) after it,
+    /// returning the operation's value and the difference. This costs up to two
+    /// extra requests; with [`RenamedClientBuilder::cache_user()`] the first
+    /// read is usually served from the cache. Other activity on the account
+    /// while the operation runs is included in the count.
+    ///
+    /// # Errors
+    ///
+    /// Returns the operation's error, or the error of either balance read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let (result, spent) = client
+    ///     .with_cost_tracking(|c| c.rename("invoice.pdf", None))
+    ///     .await?;
+    /// println!("{} cost {} credits", result.suggested_filename, spent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_cost_tracking<'a, T, F, Fut>(&'a self, operation: F) -> Result<(T, u32)>
+    where
+        F: FnOnce(&'a RenamedClient) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let before = self.get_user().await?.credits.unwrap_or_default();
+        let value = operation(self).await?;
+        let after = self.get_user_fresh().await?.credits.unwrap_or_default();
+
+        let spent = i64::from(before.get()) - i64::from(after.get());
+        Ok((value, u32::try_from(spent.max(0)).unwrap_or(u32::MAX)))
+    }
+
+    /// Renames a file using AI.
+    ///
+    /// Analyzes the file content and suggests an appropriate filename.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - Path to the file to rename.
+    /// * `options` - Optional configuration for the rename operation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::{RenamedClient, RenameOptions};
+    ///
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// let client = RenamedClient::new("rt_your_api_key");
+    ///
+    /// // Basic usage
+    /// let result = client.rename("document.pdf", None).await?;
+    /// println!("Suggested: {}", result.suggested_filename);
+    ///
+    /// // With custom template
+    /// let options = RenameOptions::new().with_template("{date}_{type}_{vendor}");
+    /// let result = client.rename("invoice.pdf", Some(options)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename(
+        &self,
+        file: impl AsRef<Path>,
+        options: Option<RenameOptions>,
+    ) -> Result<RenameResult> {
+        let format = self.rename_format(options.as_ref());
+        let fields = self.rename_fields(options, &Self::upload_filename(file.as_ref()));
+
+        let body = self.upload_file("/rename", file, fields).await?;
+        Self::parse_rename(&body, format)
+    }
+
+    /// Renames a file and records the result in a sidecar JSON file.
+    ///
+    /// Same as [`rename()`](Self::rename), then writes the full
+    /// [`RenameResult`] plus a `renamedAt` Unix timestamp (in seconds) to
+    /// `{file}.renamed.json` next to the file, e.g. for audit trails. An
+    /// existing sidecar is overwritten. The file itself is not renamed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::File`] if the sidecar can't be written, in
+    /// addition to the errors of [`rename()`](Self::rename).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// // Also writes invoice.pdf.renamed.json
+    /// let result = client.rename_with_sidecar("invoice.pdf", None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_with_sidecar(
+        &self,
+        file: impl AsRef<Path>,
+        options: Option<RenameOptions>,
+    ) -> Result<RenameResult> {
+        let file = file.as_ref();
+        let result = self.rename(file, options).await?;
+
+        let mut sidecar = serde_json::to_value(&result).map_err(RenamedError::from_serde)?;
+        let renamed_at = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        if let serde_json::Value::Object(fields) = &mut sidecar {
+            fields.insert("renamedAt".to_string(), renamed_at.into());
+        }
+        let json = serde_json::to_vec_pretty(&sidecar).map_err(RenamedError::from_serde)?;
+
+        let mut path = file.as_os_str().to_owned();
+        path.push(".renamed.json");
+        let path = PathBuf::from(path);
+        tokio::fs::write(&path, json).await.map_err(|e| {
+            RenamedError::from_io(e, format!("Failed to write sidecar {}", path.display()))
+        })?;
+
+        Ok(result)
+    }
+
+    /// Renames a document the API fetches from a URL.
+    ///
+    /// Same as [`rename()`](Self::rename), but the server downloads the file
+    /// from `url` (http or https) itself, saving a download and re-upload for
+    /// documents that are already hosted somewhere.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let result = client
+    ///     .rename_url("https://files.example.com/inbox/scan-0042.pdf", None)
+    ///     .await?;
+    /// println!("Suggested: {}", result.suggested_filename);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_url(
+        &self,
+        url: &str,
+        options: Option<RenameOptions>,
+    ) -> Result<RenameResult> {
+        let format = self.rename_format(options.as_ref());
+        let fields = self.rename_fields(options, &Self::remote_filename(url));
+
+        let body = self
+            .fetch_remote("/rename-from-url", "fileUrl", url, fields)
+            .await?;
+        Self::parse_rename(&body, format)
+    }
+
+    /// Renames a file with explicit cancellation support.
+    ///
+    /// Same as [`rename()`](Self::rename), but also returns an [`AbortHandle`]
+    /// that cancels the upload. An aborted rename resolves with
+    /// [`RenamedError::Cancelled`]. See [`cancellable()`] to wrap other operations.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let (rename, handle) = client.rename_cancellable("scan.pdf", None);
+    /// let task = tokio::spawn(async move { rename.await });
+    ///
+    /// // The user navigated away
+    /// handle.abort();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_cancellable(
+        &self,
+        file: impl AsRef<Path> + Send + 'static,
+        options: Option<RenameOptions>,
+    ) -> (
+        impl Future<Output = Result<RenameResult>> + Send + 'static,
+        AbortHandle,
+    ) {
+        let client = self.clone();
+        cancellable(async move { client.rename(file, options).await })
+    }
+
+    /// Renames many files concurrently, collecting a result for each.
+    ///
+    /// Up to four files are uploaded at a time. A failing file doesn't stop the
+    /// rest of the batch; its error is recorded in [`BatchResult::failed`] and
+    /// can be re-run with [`BatchResult::retry_failed()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let batch = client.rename_batch(["a.pdf", "b.pdf"], None).await;
+    ///
+    /// for (path, result) in &batch.succeeded {
+    ///     println!("{} -> {}", path.display(), result.suggested_filename);
+    /// }
+    /// for (path, err) in &batch.failed {
+    ///     eprintln!("{} failed: {}", path.display(), err);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_batch<P: AsRef<Path>>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+        options: Option<RenameOptions>,
+    ) -> BatchResult {
+        self.rename_batch_with(files, options, BatchOptions::default())
+            .await
+    }
+
+    /// Renames many files concurrently, as controlled by `batch_options`.
+    ///
+    /// Same as [`rename_batch()`](Self::rename_batch), but with
+    /// [`BatchOptions::with_fail_fast()`] the batch stops on the first error
+    /// that would fail every file, such as a rejected API key.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// use renamed::BatchOptions;
+    ///
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let batch = client
+    ///     .rename_batch_with(["a.pdf", "b.pdf"], None, BatchOptions::new().with_fail_fast(true))
+    ///     .await;
+    /// if batch.is_aborted() {
+    ///     eprintln!("Aborted, {} files not processed", batch.skipped.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_batch_with<P: AsRef<Path>>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+        options: Option<RenameOptions>,
+        batch_options: BatchOptions,
+    ) -> BatchResult {
+        let paths: Vec<PathBuf> = files
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+
+        // Unordered, so an aborting error is seen as soon as it happens
+        let mut pending = stream::iter(paths.iter().cloned().enumerate())
+            .map(|(index, path)| {
+                let options = options.clone();
+                async move {
+                    let result = self.rename(&path, options).await;
+                    (index, path, result)
+                }
+            })
+            .buffer_unordered(BATCH_CONCURRENCY);
+
+        let mut outcomes = Vec::with_capacity(paths.len());
+        while let Some((index, path, result)) = pending.next().await {
+            let abort = result
+                .as_ref()
+                .is_err_and(|err| batch_options.aborts_on(err));
+            outcomes.push((index, path, result));
+            if abort {
+                break;
+            }
+        }
+        // Dropping the stream cancels the uploads still in flight
+        drop(pending);
+        outcomes.sort_by_key(|(index, _, _)| *index);
+
+        let mut batch = BatchResult::new(options, batch_options);
+        let mut done = vec![false; paths.len()];
+        for (index, path, result) in outcomes {
+            done[index] = true;
+            batch.push(path, result);
+        }
+        batch.skipped = paths
+            .into_iter()
+            .zip(done)
+            .filter(|(_, done)| !done)
+            .map(|(path, _)| path)
+            .collect();
+        batch
+    }
+
+    /// Resumes a batch, renaming only the files not in `completed`.
+    ///
+    /// Pass the full list of files and the paths already done, e.g. the
+    /// [`BatchResult::completed_paths()`] saved from earlier runs, to continue
+    /// a long batch after a pause or restart. The returned result only covers
+    /// the files renamed by this call.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(files: Vec<std::path::PathBuf>) -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// # let load_done = || Vec::<std::path::PathBuf>::new();
+    /// // Paths persisted from an earlier run's `completed_paths()`
+    /// let done = load_done();
+    /// let batch = client.rename_batch_resume(&files, &done, None).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_batch_resume<P: AsRef<Path>, C: AsRef<Path>>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+        completed: impl IntoIterator<Item = C>,
+        options: Option<RenameOptions>,
+    ) -> BatchResult {
+        let completed: HashSet<PathBuf> = completed
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        let remaining: Vec<PathBuf> = files
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .filter(|path| !completed.contains(path))
+            .collect();
+
+        self.rename_batch(remaining, options).await
+    }
+
+    /// Computes a rename plan for many files without touching them on disk.
+    ///
+    /// Runs [`rename_batch()`](Self::rename_batch) and packages each suggestion
+    /// as a [`RenamePlan`], in the order the files were given. Review the plan
+    /// (e.g. in an "old → new" preview) and carry it out with
+    /// [`apply_plan()`](crate::apply_plan).
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first file that could not be renamed.
+    pub async fn plan_renames<P: AsRef<Path>>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+        options: Option<RenameOptions>,
+    ) -> Result<Vec<RenamePlan>> {
+        let batch = self.rename_batch(files, options).await;
+        if let Some((_, err)) = batch.failed.into_iter().next() {
+            return Err(err);
+        }
+
+        Ok(batch
+            .succeeded
+            .into_iter()
+            .map(|(path, result)| RenamePlan::new(path, result))
+            .collect())
+    }
+
+    /// Renames a file from bytes.
+    ///
+    /// Same as [`rename()`](Self::rename) but accepts raw bytes instead of a file path.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The file content as bytes.
+    /// * `filename` - The filename to use (for MIME type detection).
+    /// * `options` - Optional configuration for the rename operation.
+    pub async fn rename_bytes(
+        &self,
+        content: Vec<u8>,
+        filename: &str,
+        options: Option<RenameOptions>,
+    ) -> Result<RenameResult> {
+        let mime_type = self.mime_type_for(Path::new(filename));
+        self.rename_bytes_with_mime(content, filename, &mime_type, options)
+            .await
+    }
+
+    /// Renames a file from bytes with an explicit content type.
+    ///
+    /// Same as [`rename_bytes()`](Self::rename_bytes), but sends `mime_type`
+    /// instead of guessing it from `filename`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// # let content = Vec::new();
+    /// let result = client
+    ///     .rename_bytes_with_mime(content, "scan-0001", "application/pdf", None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_bytes_with_mime(
+        &self,
+        content: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        options: Option<RenameOptions>,
+    ) -> Result<RenameResult> {
+        let format = self.rename_format(options.as_ref());
+        let fields = self.rename_fields(options, filename);
+
+        let body = self
+            .upload_bytes("/rename", content, filename, mime_type, fields)
+            .await?;
+        Self::parse_rename(&body, format)
+    }
+
+    /// Renames a file streamed from a reader, without buffering it in memory.
+    ///
+    /// `make_reader` is called once per attempt and must open a reader
+    /// positioned at the start of the content, so retries send the whole file
+    /// again. `len` is the exact content length in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::File`] if `make_reader` fails to open the
+    /// content.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let path = "large-scan.pdf";
+    /// let len = tokio::fs::metadata(path).await?.len();
+    ///
+    /// let result = client
+    ///     .rename_from_factory(|| tokio::fs::File::open(path), path, len, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_from_factory<F, Fut, R>(
+        &self,
+        make_reader: F,
+        filename: &str,
+        len: u64,
+        options: Option<RenameOptions>,
+    ) -> Result<RenameResult>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = std::io::Result<R>> + Send,
+        R: AsyncRead + Send + 'static,
+    {
+        let format = self.rename_format(options.as_ref());
+        let fields = self.rename_fields(options, filename);
+
+        let body = self
+            .upload_reader("/rename", make_reader, filename, len, fields)
+            .await?;
+        Self::parse_rename(&body, format)
+    }
+
+    /// Splits a PDF into multiple documents.
+    ///
+    /// Returns an [`AsyncJob`] that can be polled for completion. PDF splitting
+    /// is an asynchronous operation that may take some time for large documents.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - Path to the PDF file to split.
+    /// * `options` - Optional configuration for the split operation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::{RenamedClient, PdfSplitOptions, SplitMode};
+    ///
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// let client = RenamedClient::new("rt_your_api_key");
+    ///
+    /// // Auto-detect document boundaries
+    /// let job = client.pdf_split("multi-page.pdf", None).await?;
+    /// let result = job.wait(None).await?;
+    ///
+    /// for doc in result.documents {
+    ///     println!("{}: pages {}", doc.filename, doc.pages);
+    /// }
+    ///
+    /// // Split every 5 pages
+    /// let options = PdfSplitOptions::new()
+    ///     .with_mode(SplitMode::Pages)
+    ///     .with_pages_per_split(5);
+    /// let job = client.pdf_split("large.pdf", Some(options)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pdf_split(
+        &self,
+        file: impl AsRef<Path>,
+        options: Option<PdfSplitOptions>,
+    ) -> Result<AsyncJob> {
+        let (options, fields) = self.pdf_split_fields(options);
+
+        let body = self.upload_file("/pdf-split", file, fields).await?;
+        self.split_job(&body, options.as_ref())
+    }
+
+    /// Splits a PDF from bytes.
+    ///
+    /// Same as [`pdf_split()`](Self::pdf_split) but accepts raw bytes.
+    pub async fn pdf_split_bytes(
+        &self,
+        content: Vec<u8>,
+        filename: &str,
+        options: Option<PdfSplitOptions>,
+    ) -> Result<AsyncJob> {
+        let mime_type = self.mime_type_for(Path::new(filename));
+        self.pdf_split_bytes_with_mime(content, filename, &mime_type, options)
+            .await
+    }
+
+    /// Splits a PDF from bytes with an explicit content type.
+    ///
+    /// Same as [`pdf_split_bytes()`](Self::pdf_split_bytes), but sends
+    /// `mime_type` instead of guessing it from `filename`.
+    pub async fn pdf_split_bytes_with_mime(
+        &self,
+        content: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        options: Option<PdfSplitOptions>,
+    ) -> Result<AsyncJob> {
+        let (options, fields) = self.pdf_split_fields(options);
+
+        let body = self
+            .upload_bytes("/pdf-split", content, filename, mime_type, fields)
+            .await?;
+        self.split_job(&body, options.as_ref())
+    }
+
+    /// Splits a PDF the API fetches from a URL.
+    ///
+    /// Same as [`pdf_split()`](Self::pdf_split), but the server downloads the
+    /// PDF from `url` (http or https) itself instead of receiving an upload.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let job = client
+    ///     .pdf_split_url("https://files.example.com/scans/batch.pdf", None)
+    ///     .await?;
+    /// let result = job.wait(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pdf_split_url(
+        &self,
+        url: &str,
+        options: Option<PdfSplitOptions>,
+    ) -> Result<AsyncJob> {
+        let (options, fields) = self.pdf_split_fields(options);
+
+        let body = self
+            .fetch_remote("/pdf-split/from-url", "url", url, fields)
+            .await?;
+        self.split_job(&body, options.as_ref())
+    }
+
+    /// Creates the [`AsyncJob`] for a split response body, applying poll options.
+    fn split_job(&self, body: &str, options: Option<&PdfSplitOptions>) -> Result<AsyncJob> {
+        let response: PdfSplitResponse =
+            serde_json::from_str(body).map_err(RenamedError::from_serde)?;
+
+        let mut job = AsyncJob::new(self.clone(), response.status_url);
+
+        if let Some(opts) = options {
+            // The timeout is converted at the job's interval, so set that first
+            if let Some(interval) = opts.poll_interval {
+                job = job.with_poll_interval(interval);
+            }
+            if let Some(timeout) = opts.poll_timeout {
+                job = job.with_poll_timeout(timeout);
+            }
+        }
+        Ok(job)
+    }
+
+    /// Fetches the result of a completed split job by its ID.
+    ///
+    /// Useful when a webhook reports that a job finished and the original
+    /// [`AsyncJob`] is no longer around. The status is fetched once; nothing
+    /// is polled.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`RenamedError::InvalidValue`] if `job_id` contains characters
+    ///   other than ASCII letters, digits, `-` and `_`.
+    /// - Returns [`RenamedError::Job`] if the job failed or is still running.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(job_id: &str) -> Result<(), renamed::RenamedError> {
+    /// let client = renamed::RenamedClient::new("rt_your_api_key");
+    ///
+    /// let result = client.get_job_result(job_id).await?;
+    /// println!("{} documents", result.documents.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_job_result(&self, job_id: &str) -> Result<PdfSplitResult> {
+        // The id becomes a path segment, so it must not be able to add to the path
+        let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        if job_id.is_empty() || !job_id.chars().all(valid) {
+            return Err(RenamedError::InvalidValue {
+                message: format!("Invalid job id: {:?}", job_id).into(),
+                value: Some(job_id.to_string()),
+                details: None,
+            });
+        }
+
+        let status_url = self.build_url(&format!("/pdf-split/status/{}", job_id));
+        AsyncJob::new(self.clone(), status_url)
+            .completed_result()
+            .await
+    }
+
+    /// Returns the number of pages in a PDF.
+    ///
+    /// The count is read from the file's page tree locally: no request is
+    /// made and no credits are spent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::File`] if the file can't be read, and
+    /// [`RenamedError::InvalidValue`] if no page tree is found, e.g. because
+    /// the file is not a PDF or stores its page tree in a compressed object
+    /// stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let pages = client.page_count("multi-page.pdf").await?;
+    /// println!("{} pages", pages);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn page_count(&self, file: impl AsRef<Path>) -> Result<u32> {
+        let file_path = file.as_ref();
+        let content = tokio::fs::read(file_path).await.map_err(|e| {
+            RenamedError::from_io(e, format!("Failed to read file: {}", file_path.display()))
+        })?;
+        pdf::page_count(&content).ok_or_else(|| RenamedError::InvalidValue {
+            message: format!("No PDF page tree found in {}", file_path.display()).into(),
+            value: None,
+            details: None,
+        })
+    }
+
+    /// Returns the total number of pages across many PDFs.
+    ///
+    /// Counts the pages of up to four files at a time with
+    /// [`page_count()`](Self::page_count), e.g. to estimate the cost of a
+    /// batch or size a progress bar before starting it. Like `page_count()`,
+    /// this reads the files locally and makes no requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered; the remaining files are not counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let total = client.total_pages(["a.pdf", "b.pdf", "c.pdf"]).await?;
+    /// println!("Batch has {} pages", total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn total_pages<P: AsRef<Path>>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+    ) -> Result<u32> {
+        stream::iter(files)
+            .map(|path| async move { self.page_count(path).await })
+            .buffer_unordered(BATCH_CONCURRENCY)
+            .try_fold(0u32, |total, pages| async move {
+                Ok(total.saturating_add(pages))
+            })
+            .await
+    }
+
+    /// Splits a PDF, waits for the job, and downloads every output into a directory.
+    ///
+    /// This is the end-to-end split flow in one call: [`pdf_split()`](Self::pdf_split),
+    /// [`AsyncJob::wait()`], then [`download_to()`](Self::download_to) for each
+    /// document. The directory is created if it does not exist, and each
+    /// document's size is verified against [`SplitDocument::size`](crate::SplitDocument::size).
+    /// Duplicate document names are made unique as described in
+    /// [`PdfSplitResult::deduplicated_filenames()`](crate::PdfSplitResult::deduplicated_filenames).
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - Path to the PDF file to split.
+    /// * `options` - Optional configuration for the split operation.
+    /// * `out_dir` - Directory to write the split documents into.
+    /// * `on_progress` - Optional callback invoked with job status updates.
+    ///
+    /// # Returns
+    ///
+    /// The paths of the written files, in document order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let paths = client
+    ///     .pdf_split_to_dir("multi-page.pdf", None, "output", None)
+    ///     .await?;
+    ///
+    /// for path in paths {
+    ///     println!("Saved: {}", path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pdf_split_to_dir(
+        &self,
+        file: impl AsRef<Path>,
+        options: Option<PdfSplitOptions>,
+        out_dir: impl AsRef<Path>,
+        on_progress: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>> {
+        let out_dir = out_dir.as_ref();
+        let job = self.pdf_split(file, options).await?;
+        let result = job.wait(on_progress).await?;
+
+        tokio::fs::create_dir_all(out_dir).await.map_err(|e| {
+            RenamedError::from_io(
+                e,
+                format!("Failed to create directory: {}", out_dir.display()),
+            )
+        })?;
+
+        // Local names keep only the final component so a server-provided name
+        // can't escape out_dir; make them unique so documents don't overwrite
+        // each other.
+        let filenames = result.deduplicated_filenames();
+
+        let mut paths = Vec::with_capacity(result.documents.len());
+        for (doc, filename) in result.documents.iter().zip(filenames) {
+            let path = out_dir.join(filename);
+
+            if let Some(content) = &doc.content {
+                tokio::fs::write(&path, content).await.map_err(|e| {
+                    RenamedError::from_io(e, format!("Failed to write {}", path.display()))
+                })?;
+                paths.push(path);
+                continue;
+            }
+
+            let mut download_options = DownloadOptions::new();
+            if let Some(size) = u64::try_from(doc.size).ok().filter(|&s| s > 0) {
+                download_options = download_options.with_expected_size(size);
+            }
+
+            self.download_to(&doc.download_url, &path, Some(download_options))
+                .await?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    /// Splits a PDF, then renames every resulting document.
+    ///
+    /// Waits for the split job, fetches each document's bytes with
+    /// [`document_bytes()`](Self::document_bytes), and renames up to four
+    /// documents at a time with [`rename_bytes()`](Self::rename_bytes). Pairs
+    /// are returned in document order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the split, a download or a rename; the
+    /// remaining documents are not renamed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let renamed = client
+    ///     .split_and_rename("combined-scan.pdf", None, None)
+    ///     .await?;
+    ///
+    /// for (document, result) in renamed {
+    ///     println!("Pages {} -> {}", document.pages, result.suggested_filename);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn split_and_rename(
+        &self,
+        file: impl AsRef<Path>,
+        split_options: Option<PdfSplitOptions>,
+        rename_options: Option<RenameOptions>,
+    ) -> Result<Vec<(SplitDocument, RenameResult)>> {
+        let result = self
+            .pdf_split(file, split_options)
+            .await?
+            .wait(None)
+            .await?;
+
+        stream::iter(result.documents)
+            .map(|document| {
+                let options = rename_options.clone();
+                async move {
+                    let content = self.document_bytes(&document).await?;
+                    let renamed = self
+                        .rename_bytes(content, &document.local_filename(), options)
+                        .await?;
+                    Ok((document, renamed))
+                }
+            })
+            .buffered(BATCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Extracts structured data from a document.
+    ///
+    /// Uses AI to extract data matching a schema or natural language prompt.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - Path to the document to extract data from.
+    /// * `options` - Configuration specifying what to extract.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::{RenamedClient, ExtractOptions};
+    ///
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// let client = RenamedClient::new("rt_your_api_key");
+    ///
+    /// // Using natural language prompt
+    /// let options = ExtractOptions::new()
+    ///     .with_prompt("Extract invoice number, date, and total amount");
+    /// let result = client.extract("invoice.pdf", Some(options)).await?;
+    ///
+    /// println!("Extracted data: {:?}", result.data);
+    /// println!("Confidence: {:.0}%", result.confidence * 100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn extract(
+        &self,
+        file: impl AsRef<Path>,
+        options: Option<ExtractOptions>,
+    ) -> Result<ExtractResult> {
+        let (options, fields) = self.extract_fields(options)?;
+
+        let body = self.upload_file("/extract", file, fields).await?;
+        Self::parse_extract_result(&body, options.as_ref())
+    }
+
+    /// Extracts data from bytes.
+    ///
+    /// Same as [`extract()`](Self::extract) but accepts raw bytes.
+    pub async fn extract_bytes(
+        &self,
+        content: Vec<u8>,
+        filename: &str,
+        options: Option<ExtractOptions>,
+    ) -> Result<ExtractResult> {
+        let mime_type = self.mime_type_for(Path::new(filename));
+        self.extract_bytes_with_mime(content, filename, &mime_type, options)
+            .await
+    }
+
+    /// Extracts data from bytes with an explicit content type.
+    ///
+    /// Same as [`extract_bytes()`](Self::extract_bytes), but sends `mime_type`
+    /// instead of guessing it from `filename`.
+    pub async fn extract_bytes_with_mime(
+        &self,
+        content: Vec<u8>,
+        filename: &str,
+        mime_type: &str,
+        options: Option<ExtractOptions>,
+    ) -> Result<ExtractResult> {
+        let (options, fields) = self.extract_fields(options)?;
+
+        let body = self
+            .upload_bytes("/extract", content, filename, mime_type, fields)
+            .await?;
+        Self::parse_extract_result(&body, options.as_ref())
+    }
+
+    /// Extracts data from many files, writing one JSON line per file as each
+    /// extraction finishes.
+    ///
+    /// Successful files are written as
+    /// `{"file": ..., "data": ..., "confidence": ...}`, failed files as
+    /// `{"file": ..., "error": ...}` with the error from
+    /// [`RenamedError::to_log_value()`]. A failing file doesn't stop the batch.
+    /// Up to four files are extracted at a time, and lines are written in
+    /// completion order, so results never pile up in memory and a partial
+    /// output shows which files are already done.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::File`] if writing to `writer` fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// use renamed::ExtractOptions;
+    ///
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let options = ExtractOptions::new().with_prompt("Extract vendor and total");
+    /// let file = tokio::fs::File::create("results.jsonl").await.unwrap();
+    /// let out = tokio::io::BufWriter::new(file);
+    /// client
+    ///     .extract_batch_jsonl(["a.pdf", "b.pdf"], Some(options), out)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn extract_batch_jsonl<P: AsRef<Path>>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+        options: Option<ExtractOptions>,
+        mut writer: impl AsyncWrite + Unpin,
+    ) -> Result<()> {
+        let paths: Vec<PathBuf> = files
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        let to_err = |e| RenamedError::from_io(e, "Failed to write JSON lines");
+
+        let mut outcomes = stream::iter(paths)
+            .map(|path| {
+                let options = options.clone();
+                async move {
+                    let result = self.extract(&path, options).await;
+                    (path, result)
+                }
+            })
+            .buffer_unordered(BATCH_CONCURRENCY);
+
+        while let Some((path, result)) = outcomes.next().await {
+            let file = path.display().to_string();
+            let line = match result {
+                Ok(result) => serde_json::json!({
+                    "file": file,
+                    "data": result.data,
+                    "confidence": result.confidence,
+                }),
+                Err(err) => serde_json::json!({
+                    "file": file,
+                    "error": err.to_log_value(),
+                }),
+            };
+            let mut line = serde_json::to_vec(&line).map_err(|e| to_err(e.into()))?;
+            line.push(b'\n');
+            writer.write_all(&line).await.map_err(to_err)?;
+        }
+
+        writer.flush().await.map_err(to_err)
+    }
+
+    /// Downloads a file from a URL (e.g., a split document).
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to download from.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let job = client.pdf_split("document.pdf", None).await?;
+    /// let result = job.wait(None).await?;
+    ///
+    /// for doc in result.documents {
+    ///     let content = client.download_file(&doc.download_url).await?;
+    ///     tokio::fs::write(&doc.filename, content).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_file(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self.send_download(url, 0).await?;
+
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(RenamedError::from_reqwest)
+    }
+
+    /// Returns a split document's bytes, downloading them only if needed.
+    ///
+    /// Uses [`SplitDocument::content`](crate::SplitDocument::content) when the
+    /// API returned the document inline, and falls back to
+    /// [`download_file()`](Self::download_file) otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use renamed::PdfSplitOptions;
+    ///
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let options = PdfSplitOptions::new().with_inline_output(true);
+    /// let result = client.pdf_split("document.pdf", Some(options)).await?.wait(None).await?;
+    ///
+    /// for doc in &result.documents {
+    ///     let content = client.document_bytes(doc).await?;
+    ///     println!("{}: {} bytes", doc.filename, content.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn document_bytes(&self, document: &SplitDocument) -> Result<Vec<u8>> {
+        match &document.content {
+            Some(content) => Ok(content.clone()),
+            None => self.download_file(&document.download_url).await,
+        }
+    }
+
+    /// Streams the bytes of every document in a split result.
+    ///
+    /// Yields `(filename, bytes)` pairs in document order as downloads finish,
+    /// with a bounded number of downloads in flight, so outputs can be piped
+    /// elsewhere without writing them to disk or holding all of them in memory.
+    /// Filenames are made unique as described in
+    /// [`PdfSplitResult::deduplicated_filenames()`](crate::PdfSplitResult::deduplicated_filenames).
+    /// Inline documents are yielded without a download, as in
+    /// [`document_bytes()`](Self::document_bytes).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// use futures::TryStreamExt;
+    ///
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let result = client.pdf_split("multi-page.pdf", None).await?.wait(None).await?;
+    ///
+    /// let mut outputs = std::pin::pin!(client.download_split_stream(&result));
+    /// while let Some((filename, bytes)) = outputs.try_next().await? {
+    ///     println!("{}: {} bytes", filename, bytes.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_split_stream<'a>(
+        &'a self,
+        result: &'a PdfSplitResult,
+    ) -> impl Stream<Item = Result<(String, Vec<u8>)>> + 'a {
+        stream::iter(result.documents.iter().zip(result.deduplicated_filenames()))
+            .map(move |(doc, filename)| async move {
+                let bytes = self.document_bytes(doc).await?;
+                Ok((filename, bytes))
+            })
+            .buffered(BATCH_CONCURRENCY)
+    }
+
+    /// Downloads a file from a URL, reporting progress as bytes arrive.
+    ///
+    /// The callback is invoked after each received chunk with the number of
+    /// bytes downloaded so far and the total size from `Content-Length`. The
+    /// total is `None` when the server does not send a length (e.g. chunked
+    /// responses).
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to download from.
+    /// * `on_progress` - Callback receiving `(downloaded, total)` byte counts.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// # let url = "https://www.renamed.to/download/abc";
+    /// let content = client
+    ///     .download_with_progress(url, |downloaded, total| match total {
+    ///         Some(total) => println!("{}/{} bytes", downloaded, total),
+    ///         None => println!("{} bytes", downloaded),
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_with_progress(
+        &self,
+        url: &str,
+        on_progress: impl Fn(u64, Option<u64>),
+    ) -> Result<Vec<u8>> {
+        let mut response = self.send_download(url, 0).await?;
+        let total = response.content_length();
+
+        let capacity = total.unwrap_or(0).min(MAX_DOWNLOAD_PREALLOCATION);
+        let mut content = Vec::with_capacity(usize::try_from(capacity).unwrap_or(0));
+        while let Some(chunk) = response.chunk().await.map_err(RenamedError::from_reqwest)? {
+            content.extend_from_slice(&chunk);
+            on_progress(content.len() as u64, total);
+        }
+
+        Ok(content)
+    }
+
+    /// Sends an authenticated download request, optionally starting at `offset`.
+    ///
+    /// Returns the response once the status has been checked; error statuses
+    /// are converted into the matching [`RenamedError`]. A `416` to a ranged
+    /// request is returned as is, since its `Content-Range` says whether the
+    /// file is already complete.
+    ///
+    /// Like API calls, downloads fail fast during maintenance or while the
+    /// circuit is open.
+    async fn send_download(&self, url: &str, offset: u64) -> Result<reqwest::Response> {
+        self.check_gates("GET", url)?;
+        self.send_download_attempt(url, offset, &mut Attempts::default())
+            .await
+    }
+
+    /// Sends a download request without checking the maintenance gate or the
+    /// circuit breaker, for callers that already did.
+    ///
+    /// The outcome counts toward the circuit breaker, and the response status
+    /// is recorded in `attempts`.
+    async fn send_download_attempt(
+        &self,
+        url: &str,
+        offset: u64,
+        attempts: &mut Attempts,
+    ) -> Result<reqwest::Response> {
+        let result = self.send_download_request(url, offset, attempts).await;
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.record(result.as_ref().map(|_| ()));
+        }
+        result
+    }
+
+    /// Sends a download request and checks its status.
+    async fn send_download_request(
+        &self,
+        url: &str,
+        offset: u64,
+        attempts: &mut Attempts,
+    ) -> Result<reqwest::Response> {
+        let start = Instant::now();
+
+        let mut request = self.request(reqwest::Method::GET, url).await?;
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+        }
+        let request = self.apply_deadline(request)?;
+
+        let response = request.send().await.map_err(RenamedError::from_reqwest)?;
+
+        let status_code = response.status().as_u16();
+        attempts.status_code = Some(status_code);
+        let elapsed_ms = start.elapsed().as_millis();
+
+        if self.debug {
+            debug!(
+                "[Renamed] GET {} -> {} ({}ms)",
+                Self::extract_path(url),
+                status_code,
+                elapsed_ms
+            );
+        }
+
+        let range_not_satisfiable = offset > 0 && status_code == 416;
+        if status_code >= 300 && !range_not_satisfiable {
+            let body = response.text().await.map_err(RenamedError::from_reqwest)?;
+            return Err(RenamedError::from_http_status(status_code, Some(&body)));
+        }
+
+        Ok(response)
+    }
+
+    /// Downloads a file from a URL directly to disk.
+    ///
+    /// The response body is streamed to `path` chunk by chunk rather than being
+    /// buffered in memory. With [`DownloadOptions::resume`] enabled, bytes already
+    /// present at `path` are kept and only the remainder is requested via a
+    /// `Range` header; failed attempts are retried from the last written byte
+    /// as the client's retry strategy, retry budget, and deadline allow.
+    /// Servers that ignore the range request cause a full re-download.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::InvalidValue`] if the downloaded size doesn't
+    /// match [`DownloadOptions::expected_size`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to download from.
+    /// * `path` - Destination file path.
+    /// * `options` - Optional resume and size verification settings.
+    ///
+    /// # Returns
+    ///
+    /// The final size of the file in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use renamed::{DownloadOptions, RenamedClient};
+    ///
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = RenamedClient::new("api_key");
+    /// let job = client.pdf_split("document.pdf", None).await?;
+    /// let result = job.wait(None).await?;
+    ///
+    /// for doc in result.documents {
+    ///     let options = DownloadOptions::new()
+    ///         .with_resume(true)
+    ///         .with_expected_size(doc.size as u64);
+    ///     client
+    ///         .download_to(&doc.download_url, &doc.filename, Some(options))
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_to(
+        &self,
+        url: &str,
+        path: impl AsRef<Path>,
+        options: Option<DownloadOptions>,
+    ) -> Result<u64> {
+        let path = path.as_ref();
+        let options = options.unwrap_or_default();
+        let start = Instant::now();
+        let mut attempts = Attempts::default();
+
+        let result = self
+            .download_with_resume(url, path, &options, &mut attempts)
+            .await
+            .map_err(|e| e.with_elapsed(start.elapsed()));
+        self.report_completion("GET", url, start, &attempts, result.is_ok());
+        let size = result?;
+
+        if let Some(expected) = options.expected_size {
+            if size != expected {
+                return Err(RenamedError::InvalidValue {
+                    message: format!("Downloaded {} bytes but expected {} bytes", size, expected)
+                        .into(),
+                    value: Some(size.to_string()),
+                    details: None,
+                });
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Runs download attempts until one succeeds, resuming from the bytes
+    /// already on disk when `options.resume` is set.
+    async fn download_with_resume(
+        &self,
+        url: &str,
+        path: &Path,
+        options: &DownloadOptions,
+        attempts: &mut Attempts,
+    ) -> Result<u64> {
+        loop {
+            // Checked once per attempt so a half-open circuit's single trial
+            // isn't used up, and never retried
+            self.check_gates("GET", url)?;
+
+            let offset = if options.resume {
+                tokio::fs::metadata(path)
+                    .await
+                    .map(|m| m.len())
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+
+            let error = match self
+                .download_to_from_offset(url, path, offset, attempts)
+                .await
+            {
+                Ok(size) => return Ok(size),
+                Err(error) => error,
+            };
+            if !options.resume {
+                return Err(error);
+            }
+            let Some(delay) = self.retry_delay(attempts.retries, &error, "GET", url) else {
+                return Err(error);
+            };
+
+            attempts.retries += 1;
+            if self.debug {
+                warn!(
+                    "[Renamed] Download interrupted ({}), resuming attempt {} in {}ms",
+                    error,
+                    attempts.retries,
+                    delay.as_millis()
+                );
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Performs a single download attempt, requesting bytes from `offset` onward.
+    ///
+    /// Returns the total size of the file at `path` after the attempt.
+    async fn download_to_from_offset(
+        &self,
+        url: &str,
+        path: &Path,
+        mut offset: u64,
+        attempts: &mut Attempts,
+    ) -> Result<u64> {
+        let mut response = self.send_download_attempt(url, offset, attempts).await?;
+
+        if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // The range starts at or beyond the end of the remote file. The
+            // local file is only complete if it has exactly the remote length;
+            // otherwise it is stale or from another file, so start over.
+            if content_range_total(response.headers()) == Some(offset) {
+                return Ok(offset);
+            }
+            if self.debug {
+                debug!("[Renamed] Local file doesn't match remote size, restarting download");
+            }
+            offset = 0;
+            response = self.send_download_attempt(url, offset, attempts).await?;
+        }
+
+        let status_code = response.status().as_u16();
+        let resumed = offset > 0 && status_code == 206;
+        let mut file = if resumed {
+            tokio::fs::OpenOptions::new().append(true).open(path).await
+        } else {
+            tokio::fs::File::create(path).await
+        }
+        .map_err(|e| {
+            RenamedError::from_io(e, format!("Failed to open file: {}", path.display()))
+        })?;
+
+        if self.debug && offset > 0 && !resumed {
+            debug!("[Renamed] Server ignored range request, restarting download");
+        }
+
+        let mut size = if resumed { offset } else { 0 };
+        let streamed: Result<()> = async {
+            while let Some(chunk) = response.chunk().await.map_err(RenamedError::from_reqwest)? {
+                file.write_all(&chunk).await.map_err(|e| {
+                    RenamedError::from_io(e, format!("Failed to write file: {}", path.display()))
+                })?;
+                size += chunk.len() as u64;
+            }
+            Ok(())
+        }
+        .await;
+
+        // Flush even on failure so a resumed attempt sees every written byte.
+        file.flush().await.map_err(|e| {
+            RenamedError::from_io(e, format!("Failed to write file: {}", path.display()))
+        })?;
+        if let (Err(error), Some(breaker)) = (&streamed, &self.circuit_breaker) {
+            breaker.record(Err(error));
+        }
+        streamed?;
+
+        Ok(size)
+    }
+}
+
+/// Builds the `Authorization` header for `api_key`.
+///
+/// The value is marked sensitive so it is never logged. A key that isn't a
+/// valid header value, e.g. one with a trailing newline, fails with the
+/// [`RenamedError::Authentication`] the API would answer without credentials.
+fn auth_header(
+    api_key: &str,
+) -> Result<(reqwest::header::HeaderName, reqwest::header::HeaderValue)> {
+    let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", api_key))
+        .map_err(|_| RenamedError::Authentication {
+            message: "API key contains characters that can't be sent in a header".into(),
+            status_code: 401,
+        })?;
+    value.set_sensitive(true);
+    Ok((reqwest::header::AUTHORIZATION, value))
+}
+
+/// Returns the total length from a `Content-Range` header such as `bytes */1234`.
+fn content_range_total(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let value = headers.get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+    let (_, total) = value.rsplit_once('/')?;
+    total.trim().parse().ok()
+}
+
+/// Returns the `User-Agent` sent with every request, e.g. `renamed-rust/0.1.0`.
+fn user_agent() -> String {
+    format!("renamed-rust/{}", crate::VERSION)
+}
+
+/// Reads `reader` in chunks as a stream for a request body.
+fn reader_stream<R>(reader: R) -> impl Stream<Item = std::io::Result<Vec<u8>>> + Send + 'static
+where
+    R: AsyncRead + Send + 'static,
+{
+    stream::try_unfold(Box::pin(reader), |mut reader| async move {
+        let mut chunk = vec![0; UPLOAD_CHUNK_SIZE];
+        let read = reader.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        chunk.truncate(read);
+        Ok(Some((chunk, reader)))
+    })
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[test]
+    fn test_build_url() {
+        let client = RenamedClient::new("test_key");
+
+        assert_eq!(
+            client.build_url("/rename"),
+            "https://www.renamed.to/api/v1/rename"
+        );
+        assert_eq!(
+            client.build_url("rename"),
+            "https://www.renamed.to/api/v1/rename"
+        );
+        assert_eq!(
+            client.build_url("https://example.com/status"),
+            "https://example.com/status"
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let client = RenamedClient::builder("test_key")
+            .base_url("https://custom.api.com/")
+            .timeout(Duration::from_secs(60))
+            .max_retries(5)
+            .build();
+
+        assert_eq!(client.base_url, "https://custom.api.com");
+        let network = RenamedError::Network {
+            message: "connection reset".into(),
+            source: None,
+            elapsed: None,
+        };
+        assert!(client.retry_strategy.next_delay(4, &network).is_some());
+        assert!(client.retry_strategy.next_delay(5, &network).is_none());
+        assert!(!client.debug);
+    }
+
+    #[test]
+    fn test_builder_api_version() {
+        let client = RenamedClient::builder("test_key").api_version("v2").build();
+        assert_eq!(client.base_url, "https://www.renamed.to/api/v2");
+
+        let client = RenamedClient::builder("test_key")
+            .api_version("/v3/")
+            .base_url("http://localhost:3000/api/v1/")
+            .build();
+        assert_eq!(client.base_url, "http://localhost:3000/api/v3");
+
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://localhost:3000")
+            .api_version("v2")
+            .build();
+        assert_eq!(client.base_url, "http://localhost:3000/v2");
+    }
+
+    #[test]
+    fn test_disable_mime_detection() {
+        let path = Path::new("scan.pdf");
+        assert_eq!(
+            RenamedClient::new("test_key").mime_type_for(path),
+            "application/pdf"
+        );
+        assert_eq!(
+            RenamedClient::builder("test_key")
+                .disable_mime_detection(true)
+                .build()
+                .mime_type_for(path),
+            "application/octet-stream"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bytes_with_mime_variants_send_given_type() {
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(
+                200,
+                r#"{"originalFilename": "scan-0001", "suggestedFilename": "Invoice.pdf"}"#,
+            ),
+            MockResponse::new(200, r#"{"statusUrl": "http://127.0.0.1:1/status/job_1"}"#),
+            MockResponse::new(200, r#"{"data": {}, "confidence": 0.9}"#),
+        ]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", server.addr()))
+            .max_retries(0)
+            .build();
+
+        // The filename has no extension, so the type can't be guessed from it
+        let pdf = || b"%PDF".to_vec();
+        client
+            .rename_bytes_with_mime(pdf(), "scan-0001", "application/pdf", None)
+            .await
+            .unwrap();
+        client
+            .pdf_split_bytes_with_mime(pdf(), "scan-0001", "application/pdf", None)
+            .await
+            .unwrap();
+        client
+            .extract_bytes_with_mime(pdf(), "scan-0001", "application/pdf", None)
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        for request in requests {
+            assert!(request.contains(r#"filename="scan-0001""#));
+            assert!(request.contains("Content-Type: application/pdf"));
+        }
+
+        match client
+            .rename_bytes_with_mime(pdf(), "scan-0001", "not a mime type", None)
+            .await
+        {
+            Err(RenamedError::Network { message, .. }) => {
+                assert!(message.starts_with("Invalid MIME type"));
+            }
+            other => panic!("expected invalid MIME type error, got {:?}", other),
+        }
+    }
+
+    /// Serves each body as a JSON response to one connection, in order.
+    async fn serve_json(bodies: Vec<&'static str>) -> SocketAddr {
+        serve_responses(bodies.into_iter().map(|body| (200, body)).collect()).await
+    }
+
+    /// Like [`serve_json`], with a status code for each response.
+    async fn serve_responses(responses: Vec<(u16, &'static str)>) -> SocketAddr {
+        MockServer::bind()
+            .await
+            .serve(
+                responses
+                    .into_iter()
+                    .map(|(status, body)| MockResponse::new(status, body))
+                    .collect(),
+            )
+            .addr()
+    }
+
+    #[tokio::test]
+    async fn test_builder_resolve_overrides_dns() {
+        let addr = serve_json(vec![r#"{"id": "u_1", "email": "a@example.com"}"#]).await;
+
+        // The domain doesn't exist, so the request only succeeds via the override
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://api.renamed.invalid:{}/api/v1", addr.port()))
+            .resolve("api.renamed.invalid", addr)
+            .max_retries(0)
+            .build();
+
+        let user = client.get_user().await.unwrap();
+        assert_eq!(user.email, "a@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_with_cost_tracking_reports_credits_spent() {
+        let addr = serve_json(vec![
+            r#"{"id": "u_1", "email": "a@example.com", "credits": 10}"#,
+            r#"{"id": "u_1", "email": "a@example.com", "credits": 7}"#,
+        ])
+        .await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        let (value, spent) = client
+            .with_cost_tracking(|_| async { Ok("done") })
+            .await
+            .unwrap();
+        assert_eq!(value, "done");
+        assert_eq!(spent, 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_job_result() {
+        let addr = serve_json(vec![
+            r#"{"jobId": "job_1", "status": "completed", "result": {"originalFilename": "scan.pdf", "documents": [], "totalPages": 3}}"#,
+            r#"{"jobId": "job_2", "status": "processing", "progress": 40}"#,
+        ])
+        .await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .build();
+
+        let result = client.get_job_result("job_1").await.unwrap();
+        assert_eq!(result.total_pages, 3);
+
+        match client.get_job_result("job_2").await {
+            Err(RenamedError::Job { job_id, .. }) => {
+                assert_eq!(job_id.as_deref(), Some("job_2"));
+            }
+            other => panic!("expected job error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_job_result_rejects_unsafe_ids() {
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://127.0.0.1:1")
+            .max_retries(0)
+            .build();
+
+        for job_id in ["", "../user", "job_1?x=1", "job 1"] {
+            match client.get_job_result(job_id).await {
+                Err(RenamedError::InvalidValue { value, .. }) => {
+                    assert_eq!(value.as_deref(), Some(job_id));
+                }
+                other => panic!("expected invalid value, got {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_from_factory_reopens_reader_per_attempt() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let addr = serve_responses(vec![
+            (503, r#"{"error": "Unavailable"}"#),
+            (
+                200,
+                r#"{"originalFilename": "scan.pdf", "suggestedFilename": "Invoice.pdf"}"#,
+            ),
+        ])
+        .await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .retry_on(RetryPolicy::all())
+            .max_retries(1)
+            .build();
+
+        let opened = AtomicU32::new(0);
+        let content = b"%PDF-1.4";
+        let result = client
+            .rename_from_factory(
+                || {
+                    opened.fetch_add(1, Ordering::SeqCst);
+                    std::future::ready(Ok(&content[..]))
+                },
+                "scan.pdf",
+                content.len() as u64,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.suggested_filename, "Invoice.pdf");
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rename_from_factory_reports_open_failure() {
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://127.0.0.1:1/api/v1")
+            .max_retries(0)
+            .build();
+
+        let err = client
+            .rename_from_factory(
+                || tokio::fs::File::open("/nonexistent/renamed-factory.pdf"),
+                "scan.pdf",
+                0,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.io_error_kind(), Some(std::io::ErrorKind::NotFound));
+    }
+
+    #[tokio::test]
+    async fn test_every_request_path_sends_auth_header() {
+        let server = MockServer::bind().await;
+        let addr = server.addr();
+        let result_url = format!("http://{}/api/v1/results/job_1", addr);
+        let server = server.serve(vec![
+            MockResponse::new(200, r#"{"id": "u_1", "email": "a@example.com"}"#),
+            MockResponse::new(200, "file contents"),
+            MockResponse::new(
+                200,
+                format!(
+                    r#"{{"jobId": "job_1", "status": "completed", "resultUrl": "{}"}}"#,
+                    result_url
+                ),
+            ),
+            MockResponse::new(
+                200,
+                r#"{"originalFilename": "a.pdf", "documents": [], "totalPages": 1}"#,
+            ),
+        ]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        client.get_user().await.unwrap();
+        client
+            .download_file(&format!("http://{}/api/v1/download/a.pdf", addr))
+            .await
+            .unwrap();
+        client.get_job_result("job_1").await.unwrap();
+
+        // API request, download, job status, and job result
+        let authorized: Vec<bool> = server
+            .requests()
+            .iter()
+            .map(|request| {
+                request
+                    .to_lowercase()
+                    .contains("authorization: bearer test_key")
+            })
+            .collect();
+        assert_eq!(authorized, vec![true; 4]);
+    }
+
+    #[tokio::test]
+    async fn test_unsendable_api_key_is_an_authentication_error() {
+        let client = RenamedClient::builder("rt_key\n")
+            .base_url("http://127.0.0.1:1")
+            .max_retries(0)
+            .build();
+
+        // Fails before connecting, which would be a network error
+        assert!(matches!(
+            client.get_user().await,
+            Err(RenamedError::Authentication { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_503_fails_later_requests_fast() {
+        // A second response is scripted so that a request slipping through
+        // would be answered and show up in the count
+        let maintenance = || {
+            MockResponse::new(503, r#"{"error": "Scheduled maintenance"}"#)
+                .with_header("Retry-After", "120")
+        };
+        let server = MockServer::bind()
+            .await
+            .serve(vec![maintenance(), maintenance()]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", server.addr()))
+            .retry_on(RetryPolicy::all())
+            .build();
+
+        for _ in 0..2 {
+            match client.clone().get_user().await {
+                Err(RenamedError::ServiceUnavailable { retry_after, .. }) => {
+                    assert!(matches!(retry_after, Some(119..=120)));
+                }
+                other => panic!("expected service unavailable, got {:?}", other),
+            }
+        }
+
+        // Neither a retry nor the second call reached the server
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_absurd_retry_after_is_clamped() {
+        let server = MockServer::bind().await.serve(vec![MockResponse::new(
+            503,
+            r#"{"error": "Scheduled maintenance"}"#,
+        )
+        .with_header("Retry-After", "99999999999999999999")]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", server.addr()))
+            .build();
+
+        match client.get_user().await {
+            Err(RenamedError::ServiceUnavailable { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(24 * 60 * 60));
+            }
+            other => panic!("expected service unavailable, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_body_read_failure_counts_toward_circuit_breaker() {
+        let server =
+            MockServer::bind()
+                .await
+                .serve(vec![MockResponse::new(200, r#"{"id": "u_1"}"#).truncated()]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", server.addr()))
+            .max_retries(0)
+            .circuit_breaker(1, Duration::from_secs(60))
+            .build();
+
+        assert!(matches!(
+            client.get_user().await,
+            Err(RenamedError::Network { .. })
+        ));
+        match client.get_user().await {
+            Err(RenamedError::Network { message, .. }) => {
+                assert!(message.starts_with("Circuit open"));
+            }
+            other => panic!("expected open circuit, got {:?}", other),
+        }
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unfollowed_redirect_is_an_error() {
+        let addr = serve_responses(vec![(302, "")]).await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .follow_redirects(false)
+            .max_retries(0)
+            .build();
+
+        match client.get_user().await {
+            Err(RenamedError::Api { status_code, .. }) => assert_eq!(status_code, 302),
+            other => panic!("expected API error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_split_and_rename_pairs_documents_with_names() {
+        let server = MockServer::bind().await;
+        let status_url = format!("http://{}/api/v1/pdf-split/status/job_1", server.addr());
+        let server = server.serve(vec![
+            MockResponse::new(200, format!(r#"{{"statusUrl": "{}"}}"#, status_url)),
+            MockResponse::new(
+                200,
+                r#"{"jobId": "job_1", "status": "completed", "result": {
+                "originalFilename": "scan.pdf", "totalPages": 2, "documents": [{"index": 0,
+                "filename": "part-1.pdf", "pages": "1", "downloadUrl": "", "size": 4, "content": "JVBERg=="}]}}"#,
+            ),
+            MockResponse::new(
+                200,
+                r#"{"originalFilename": "part-1.pdf", "suggestedFilename": "2024-03-01_Acme_Invoice.pdf"}"#,
+            ),
+        ]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", server.addr()))
+            .max_retries(0)
+            .build();
+
+        let file = std::env::temp_dir().join(format!("renamed-split-{}.pdf", std::process::id()));
+        tokio::fs::write(&file, b"%PDF").await.unwrap();
+        let pairs = client.split_and_rename(&file, None, None).await;
+        tokio::fs::remove_file(&file).await.unwrap();
+
+        let pairs = pairs.unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0.pages, "1");
+        assert_eq!(pairs[0].1.suggested_filename, "2024-03-01_Acme_Invoice.pdf");
+    }
+
+    #[tokio::test]
+    async fn test_rename_with_sidecar_writes_result_next_to_file() {
+        let addr = serve_json(vec![
+            r#"{"originalFilename": "scan.pdf", "suggestedFilename": "2024-03-01_Acme.pdf", "confidence": 0.9}"#,
+        ])
+        .await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        let dir = std::env::temp_dir().join(format!("renamed-sidecar-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let file = dir.join("scan.pdf");
+        tokio::fs::write(&file, b"%PDF").await.unwrap();
+
+        let result = client.rename_with_sidecar(&file, None).await;
+        let sidecar = tokio::fs::read(dir.join("scan.pdf.renamed.json")).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert_eq!(result.unwrap().suggested_filename, "2024-03-01_Acme.pdf");
+        let sidecar: serde_json::Value = serde_json::from_slice(&sidecar.unwrap()).unwrap();
+        assert_eq!(sidecar["suggestedFilename"], "2024-03-01_Acme.pdf");
+        assert_eq!(sidecar["confidence"], 0.9);
+        assert!(sidecar["renamedAt"].as_u64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_total_pages_sums_page_counts() {
+        // Nothing listens here, so any request would fail
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://127.0.0.1:1/api/v1")
+            .max_retries(0)
+            .build();
+
+        let dir = std::env::temp_dir().join(format!("renamed-pages-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let files: Vec<PathBuf> = [3, 5]
+            .iter()
+            .map(|count| {
+                let file = dir.join(format!("{}.pdf", count));
+                let pdf = format!(
+                    "%PDF-1.4\n2 0 obj << /Type /Pages /Count {} >> endobj",
+                    count
+                );
+                std::fs::write(&file, pdf).unwrap();
+                file
+            })
+            .collect();
+        let unreadable = dir.join("scan.jpg");
+        tokio::fs::write(&unreadable, b"\xff\xd8\xff")
+            .await
+            .unwrap();
+
+        let total = client.total_pages(&files).await;
+        let missing = client.total_pages([dir.join("missing.pdf")]).await;
+        let not_pdf = client.page_count(&unreadable).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert_eq!(total.unwrap(), 8);
+        assert!(matches!(missing, Err(RenamedError::File { .. })));
+        assert!(matches!(not_pdf, Err(RenamedError::InvalidValue { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_extract_batch_jsonl_writes_a_line_per_file() {
+        let addr = serve_json(vec![r#"{"data": {"total": 42}, "confidence": 0.9}"#]).await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        let dir = std::env::temp_dir().join(format!("renamed-jsonl-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let receipt = dir.join("receipt.pdf");
+        tokio::fs::write(&receipt, b"%PDF").await.unwrap();
+        let missing = dir.join("missing.pdf");
+
+        let mut out = Vec::new();
+        client
+            .extract_batch_jsonl([&receipt, &missing], None, &mut out)
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        let line_for = |path: &PathBuf| {
+            let file = path.display().to_string();
+            lines
+                .iter()
+                .find(|line| line["file"] == file.as_str())
+                .unwrap()
+        };
+        assert_eq!(line_for(&receipt)["data"]["total"], 42);
+        assert_eq!(line_for(&receipt)["confidence"], 0.9);
+        assert_eq!(line_for(&missing)["error"]["type"], "file");
+    }
+
+    #[tokio::test]
+    async fn test_rename_batch_fail_fast_skips_remaining_files() {
+        // Only the first request is answered; the rest stay in flight
+        let addr = MockServer::bind()
+            .await
+            .hold_unanswered()
+            .serve(vec![MockResponse::new(
+                401,
+                r#"{"error": "Invalid API key"}"#,
+            )])
+            .addr();
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        let dir = std::env::temp_dir().join(format!("renamed-fail-fast-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let files: Vec<PathBuf> = (0..3).map(|i| dir.join(format!("{}.pdf", i))).collect();
+        for file in &files {
+            tokio::fs::write(file, b"%PDF").await.unwrap();
+        }
+
+        let batch = client
+            .rename_batch_with(&files, None, BatchOptions::new().with_fail_fast(true))
+            .await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert!(batch.is_aborted());
+        assert_eq!(batch.failed.len(), 1);
+        assert!(matches!(
+            batch.failed[0].1,
+            RenamedError::Authentication { .. }
+        ));
+        assert_eq!(batch.skipped.len(), 2);
+    }
 
     #[test]
-    fn test_build_url() {
+    fn test_user_agent_includes_version() {
+        assert_eq!(crate::VERSION, env!("CARGO_PKG_VERSION"));
+        assert_eq!(user_agent(), format!("renamed-rust/{}", crate::VERSION));
+    }
+
+    #[tokio::test]
+    async fn test_ping_measures_round_trip() {
+        let addr = serve_json(vec![r#"{"id": "u_1", "email": "a@example.com"}"#]).await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        let latency = client.ping().await.unwrap();
+        assert!(latency > Duration::ZERO);
+
+        // The mock is exhausted, so the next ping fails to connect
+        assert!(client.ping().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_is_shared_across_requests() {
+        let addr = serve_responses(vec![
+            (503, r#"{"error": "Unavailable"}"#),
+            (503, r#"{"error": "Unavailable"}"#),
+            (503, r#"{"error": "Unavailable"}"#),
+        ])
+        .await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .retry_on(RetryPolicy::all())
+            .max_retries(5)
+            .retry_budget(1)
+            .build();
+
+        // The first request spends the only retry, the second gets none
+        assert!(client.get_user_fresh().await.is_err());
+        let start = Instant::now();
+        let err = client.get_user_fresh().await.unwrap_err();
+        assert!(matches!(
+            err,
+            RenamedError::Api {
+                status_code: 503,
+                ..
+            }
+        ));
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_upload_budget_releases_after_each_upload() {
+        let delay = Duration::from_millis(100);
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(
+                200,
+                r#"{"originalFilename": "a.pdf", "suggestedFilename": "A.pdf"}"#,
+            )
+            .with_delay(delay),
+            MockResponse::new(
+                200,
+                r#"{"originalFilename": "b.pdf", "suggestedFilename": "B.pdf"}"#,
+            )
+            .with_delay(delay),
+        ]);
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", server.addr()))
+            .max_retries(0)
+            .max_inflight_bytes(1024)
+            .build();
+
+        // Both uploads exceed the budget, so they run one after the other
+        let (a, b) = tokio::time::timeout(
+            Duration::from_secs(5),
+            futures::future::join(
+                client.rename_bytes(vec![0; 4096], "a.pdf", None),
+                client.clone().rename_bytes(vec![0; 4096], "b.pdf", None),
+            ),
+        )
+        .await
+        .unwrap();
+        assert!(a.is_ok());
+        assert!(b.is_ok());
+        assert_eq!(server.max_in_flight(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rename_batch_resume_skips_completed_files() {
+        let addr = serve_json(vec![
+            r#"{"originalFilename": "b.pdf", "suggestedFilename": "Invoice.pdf"}"#,
+        ])
+        .await;
+        let client = RenamedClient::builder("test_key")
+            .base_url(format!("http://{}/api/v1", addr))
+            .max_retries(0)
+            .build();
+
+        let dir = std::env::temp_dir().join(format!("renamed-resume-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let files = [dir.join("a.pdf"), dir.join("b.pdf")];
+        for file in &files {
+            tokio::fs::write(file, b"%PDF").await.unwrap();
+        }
+
+        let batch = client.rename_batch_resume(&files, &files[..1], None).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert!(batch.is_complete());
+        assert_eq!(batch.completed_paths(), vec![files[1].clone()]);
+    }
+
+    #[test]
+    fn test_builder_proxy() {
+        assert!(RenamedClient::builder("test_key")
+            .proxy("http://proxy.internal:3128")
+            .is_ok());
+        assert!(matches!(
+            RenamedClient::builder("test_key").proxy("not a url"),
+            Err(RenamedError::Network { .. })
+        ));
+    }
+
+    #[cfg(feature = "socks")]
+    #[test]
+    fn test_builder_socks_proxy() {
+        assert!(RenamedClient::builder("test_key")
+            .proxy("socks5://127.0.0.1:1080")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builder_with_debug() {
+        let client = RenamedClient::builder("test_key").with_debug(true).build();
+
+        assert!(client.debug);
+        assert!(client.is_debug_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_resolves_with_cancelled_error() {
+        let (future, handle) = cancellable(async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        });
+
+        handle.abort();
+        assert!(matches!(future.await, Err(RenamedError::Cancelled { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_resumed_download_checks_416_against_remote_length() {
+        let dir = std::env::temp_dir().join(format!("renamed-resume-416-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("a.pdf");
+        let options = || Some(DownloadOptions::new().with_resume(true));
+        let client = RenamedClient::builder("test_key").max_retries(0).build();
+
+        // The local file has the remote length, so it is complete
+        tokio::fs::write(&path, b"%PDF").await.unwrap();
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(416, "").with_header("Content-Range", "bytes */4")
+        ]);
+        let url = format!("http://{}/download/a.pdf", server.addr());
+        let complete = client.download_to(&url, &path, options()).await;
+
+        // A longer local file is stale, so the download restarts from zero
+        tokio::fs::write(&path, b"%PDF-stale").await.unwrap();
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(416, "").with_header("Content-Range", "bytes */4"),
+            MockResponse::new(200, "%PDF"),
+        ]);
+        let url = format!("http://{}/download/a.pdf", server.addr());
+        let restarted = client.download_to(&url, &path, options()).await;
+        let content = tokio::fs::read(&path).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert_eq!(complete.unwrap(), 4);
+        assert_eq!(restarted.unwrap(), 4);
+        assert_eq!(content.unwrap(), b"%PDF");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_resumed_download_goes_through_retry_strategy() {
+        let dir = std::env::temp_dir().join(format!("renamed-resume-retry-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("a.pdf");
+        let metrics = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&metrics);
+        let client = RenamedClient::builder("test_key")
+            .max_retries(1)
+            .on_complete(move |m| sink.lock().unwrap().push(m.clone()))
+            .build();
+
+        // The first response drops after two bytes; the retry asks for the rest
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(200, "%P").truncated(),
+            MockResponse::new(206, "DF").with_header("Content-Range", "bytes 2-3/4"),
+        ]);
+        let url = format!("http://{}/download/a.pdf", server.addr());
+        let options = DownloadOptions::new()
+            .with_resume(true)
+            .with_expected_size(4);
+        let size = client.download_to(&url, &path, Some(options)).await;
+
+        // A size that doesn't match is a validation failure, not a network one
+        let server = MockServer::bind()
+            .await
+            .serve(vec![MockResponse::new(200, "%PDF")]);
+        let url = format!("http://{}/download/a.pdf", server.addr());
+        let options = DownloadOptions::new().with_expected_size(5);
+        let mismatch = client.download_to(&url, &path, Some(options)).await;
+        let content = tokio::fs::read(&path).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert_eq!(size.unwrap(), 4);
+        assert_eq!(content.unwrap(), b"%PDF");
+        let metrics = metrics.lock().unwrap();
+        assert_eq!(metrics[0].retries, 1);
+        assert_eq!(metrics[0].status_code, Some(206));
+        assert!(metrics[0].success);
+        assert!(matches!(
+            mismatch,
+            Err(RenamedError::InvalidValue { value: Some(ref v), .. }) if v == "4"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_to_fails_fast_while_circuit_is_open() {
+        let dir =
+            std::env::temp_dir().join(format!("renamed-download-open-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("a.pdf");
+        let client = RenamedClient::builder("test_key")
+            .circuit_breaker(1, Duration::from_secs(60))
+            .build();
+
+        // The second response is only served if a request slips through
+        let server = MockServer::bind().await.serve(vec![
+            MockResponse::new(500, r#"{"error": "Storage unavailable"}"#),
+            MockResponse::new(200, "%PDF"),
+        ]);
+        let url = format!("http://{}/download/a.pdf", server.addr());
+        let options = || Some(DownloadOptions::new().with_resume(true));
+        let first = client.download_to(&url, &path, options()).await;
+        let second = client.download_to(&url, &path, options()).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert!(matches!(
+            first,
+            Err(RenamedError::Api {
+                status_code: 500,
+                ..
+            })
+        ));
+        match second {
+            Err(RenamedError::Network { message, .. }) => {
+                assert!(message.starts_with("Circuit open"));
+            }
+            other => panic!("expected circuit open error, got {:?}", other),
+        }
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_split_stream_yields_in_order() {
         let client = RenamedClient::new("test_key");
+        let result: PdfSplitResult = serde_json::from_str(
+            r#"{"originalFilename": "scan.pdf", "totalPages": 2, "documents": [
+                {"index": 0, "filename": "a.pdf", "pages": "1", "downloadUrl": "", "size": 1, "content": "MQ=="},
+                {"index": 1, "filename": "a.pdf", "pages": "2", "downloadUrl": "", "size": 1, "content": "Mg=="}]}"#,
+        )
+        .unwrap();
+
+        let outputs: Vec<(String, Vec<u8>)> = client
+            .download_split_stream(&result)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()
+            .unwrap();
 
         assert_eq!(
-            client.build_url("/rename"),
-            "https://www.renamed.to/api/v1/rename"
-        );
-        assert_eq!(
-            client.build_url("rename"),
-            "https://www.renamed.to/api/v1/rename"
+            outputs,
+            vec![
+                ("a.pdf".to_string(), b"1".to_vec()),
+                ("a_2.pdf".to_string(), b"2".to_vec())
+            ]
         );
+    }
+
+    #[tokio::test]
+    async fn test_on_complete_reports_retries() {
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+
+        // Nothing listens on port 1, so every attempt fails to connect
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://127.0.0.1:1")
+            .max_retries(1)
+            .on_complete(move |metrics| sink.lock().unwrap().push(metrics.clone()))
+            .build();
+
+        let elapsed = match client.get_user().await {
+            Err(RenamedError::Network { elapsed, .. }) => elapsed.unwrap(),
+            other => panic!("Expected Network error, got {:?}", other),
+        };
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        assert!(elapsed > Duration::ZERO && elapsed <= reported[0].elapsed);
+        assert_eq!(reported[0].method, "GET");
+        assert_eq!(reported[0].retries, 1);
+        assert_eq!(reported[0].status_code, None);
+        assert!(!reported[0].success);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_factory_per_request() {
+        let counter = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let next = Arc::clone(&counter);
+        let client = RenamedClient::builder("test_key")
+            .request_id_factory(Arc::new(move || {
+                let n = next.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                format!("trace-{}", n)
+            }))
+            .build();
+
+        for expected in ["trace-0", "trace-1"] {
+            let request = client
+                .request(reqwest::Method::GET, "/user")
+                .await
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(request.headers()[REQUEST_ID_HEADER], expected);
+        }
+
+        let request = RenamedClient::new("test_key")
+            .request(reqwest::Method::GET, "/user")
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(request.headers().get(REQUEST_ID_HEADER).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_id_sent_with_job_polls_and_downloads() {
+        let server = MockServer::bind().await;
+        let status_url = format!("http://{}/api/v1/pdf-split/status/job_1", server.addr());
+        let server = server.serve(vec![
+            MockResponse::new(200, r#"{"jobId": "job_1", "status": "processing"}"#),
+            MockResponse::new(200, "%PDF"),
+        ]);
+        let client = RenamedClient::builder("test_key")
+            .request_id_factory(Arc::new(|| "trace-1".to_string()))
+            .build();
+
+        AsyncJob::new(client.clone(), status_url.clone())
+            .status()
+            .await
+            .unwrap();
+        client.download_file(&status_url).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            assert!(request.to_lowercase().contains("x-request-id: trace-1"));
+        }
+    }
+
+    #[test]
+    fn test_describe_fields_redacts_file() {
+        let fields = vec![
+            ("template", "{date}".to_string()),
+            ("mode", "auto".to_string()),
+        ];
         assert_eq!(
-            client.build_url("https://example.com/status"),
-            "https://example.com/status"
+            RenamedClient::describe_fields(&fields),
+            r#"file=<redacted>, template="{date}", mode="auto""#
         );
     }
 
+    #[tokio::test]
+    async fn test_user_cache_invalidated_by_upload() {
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://127.0.0.1:1")
+            .max_retries(0)
+            .cache_user(Duration::from_secs(60))
+            .build();
+        let user: User =
+            serde_json::from_str(r#"{"id": "u_1", "email": "a@example.com", "credits": 5}"#)
+                .unwrap();
+        client.user_cache.as_ref().unwrap().set(user);
+
+        // Served from the cache without touching the (unreachable) API
+        assert_eq!(client.get_user().await.unwrap().id, "u_1");
+        assert!(client.get_user_fresh().await.is_err());
+        assert!(client.get_user().await.is_ok());
+
+        assert!(client
+            .rename_bytes(b"%PDF".to_vec(), "a.pdf", None)
+            .await
+            .is_err());
+        assert!(client.get_user().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline() {
+        let client = RenamedClient::builder("test_key")
+            .base_url("http://127.0.0.1:1")
+            .max_retries(0)
+            .build();
+
+        let expired = client.with_deadline(Instant::now());
+        assert!(matches!(
+            expired.get_user().await,
+            Err(RenamedError::Timeout { .. })
+        ));
+        assert!(client.deadline.is_none());
+
+        // Requests are bounded by the deadline, not the 30s client timeout
+        let request = client
+            .with_deadline(Instant::now() + Duration::from_secs(2))
+            .apply_deadline(client.client.get("http://127.0.0.1:1"))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(request.timeout().unwrap() <= &Duration::from_secs(2));
+    }
+
     #[test]
-    fn test_builder() {
+    fn test_sandbox_client() {
+        let client = RenamedClient::sandbox("test_key");
+        assert!(client.is_sandbox());
+        assert!(!RenamedClient::new("test_key").is_sandbox());
+    }
+
+    #[test]
+    fn test_close_reports_shared_pool() {
+        let client = RenamedClient::new("test_key");
+        let clone = client.clone();
+
+        assert!(!client.close());
+        assert!(clone.close());
+    }
+
+    #[test]
+    fn test_default_options_merge_with_per_call() {
         let client = RenamedClient::builder("test_key")
-            .base_url("https://custom.api.com/")
-            .timeout(Duration::from_secs(60))
-            .max_retries(5)
+            .default_rename_options(RenameOptions::new().with_template("{date}"))
+            .default_extract_options(ExtractOptions::new().with_prompt("Extract totals"))
             .build();
 
-        assert_eq!(client.base_url, "https://custom.api.com");
-        assert_eq!(client.max_retries, 5);
-        assert!(!client.debug);
+        assert_eq!(
+            client.rename_fields(None, "scan.pdf"),
+            vec![("template", "{date}".to_string())]
+        );
+        assert_eq!(
+            client.rename_fields(
+                Some(RenameOptions::new().with_template("{vendor}")),
+                "scan.pdf"
+            ),
+            vec![("template", "{vendor}".to_string())]
+        );
+        assert_eq!(
+            client.rename_fields(
+                Some(RenameOptions::new().use_original_name_as_hint(true)),
+                "2024-01-31_scan.pdf"
+            ),
+            vec![
+                ("template", "{date}".to_string()),
+                ("hint", "2024-01-31_scan.pdf".to_string())
+            ]
+        );
+        assert_eq!(
+            client.rename_fields(
+                Some(RenameOptions::new().include_metadata(true)),
+                "scan.pdf"
+            ),
+            vec![
+                ("template", "{date}".to_string()),
+                ("includeMetadata", "true".to_string())
+            ]
+        );
+        assert_eq!(client.rename_format(None), None);
+        assert_eq!(
+            client.rename_format(Some(&RenameOptions::new().with_separator('_'))),
+            Some((Case::Preserve, Some('_')))
+        );
+        assert_eq!(
+            client.extract_fields(None).unwrap().1,
+            vec![("prompt", "Extract totals".to_string())]
+        );
+        assert_eq!(
+            client
+                .extract_fields(Some(
+                    ExtractOptions::new()
+                        .with_output_language("en")
+                        .with_context("Sent by ACME")
+                ))
+                .unwrap()
+                .1,
+            vec![
+                ("prompt", "Extract totals".to_string()),
+                ("outputLanguage", "en".to_string()),
+                ("context", "Sent by ACME".to_string())
+            ]
+        );
+        assert!(client.pdf_split_fields(None).1.is_empty());
+        assert_eq!(
+            client
+                .pdf_split_fields(Some(PdfSplitOptions::new().with_inline_output(true)))
+                .1,
+            vec![("inlineOutput", "true".to_string())]
+        );
     }
 
     #[test]
-    fn test_builder_with_debug() {
-        let client = RenamedClient::builder("test_key").with_debug(true).build();
+    fn test_remote_fetch_helpers() {
+        assert_eq!(
+            RenamedClient::remote_filename("https://files.example.com/in/scan.pdf?sig=abc"),
+            "scan.pdf"
+        );
+        assert_eq!(
+            RenamedClient::remote_filename("https://files.example.com"),
+            "file"
+        );
+        assert_eq!(
+            RenamedClient::remote_filename("https://files.example.com/in/"),
+            "file"
+        );
 
-        assert!(client.debug);
-        assert!(client.is_debug_enabled());
+        assert_eq!(
+            RenamedClient::json_field_value("pagesPerSplit", "3".to_string()),
+            serde_json::json!(3)
+        );
+        assert_eq!(
+            RenamedClient::json_field_value("inlineOutput", "true".to_string()),
+            serde_json::json!(true)
+        );
+        assert_eq!(
+            RenamedClient::json_field_value("hint", "2024".to_string()),
+            serde_json::json!("2024")
+        );
     }
 
     #[test]
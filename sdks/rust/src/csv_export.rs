@@ -0,0 +1,160 @@
+//! Export extracted data from many documents as CSV.
+//!
+//! Requires the `csv` feature.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde_json::{Map, Value};
+
+use crate::batch::BATCH_CONCURRENCY;
+use crate::client::RenamedClient;
+use crate::error::{RenamedError, Result};
+use crate::models::{ExtractOptions, ExtractResult};
+
+/// Extracts data from each file and writes it as CSV rows.
+///
+/// The first column, `file`, holds the path of the source document. The
+/// remaining columns are the union of all extracted keys, in sorted order.
+/// Each row holds one file's data; keys a file didn't return are left empty.
+/// When a file's data is an array of records (e.g. line items), it gets one
+/// row per record.
+/// String values are written as-is, `null` as an empty cell, and any other
+/// value as JSON.
+///
+/// Files are extracted concurrently. Nothing is written unless every
+/// extraction succeeds.
+///
+/// # Errors
+///
+/// Returns the first extraction error encountered, or
+/// [`RenamedError::File`] if writing the CSV fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> Result<(), renamed::RenamedError> {
+/// use renamed::{ExtractOptions, RenamedClient};
+///
+/// let client = RenamedClient::new("rt_your_api_key");
+/// let options = ExtractOptions::new().with_prompt("Extract vendor, date, and total");
+///
+/// let file = std::fs::File::create("receipts.csv").unwrap();
+/// renamed::extract_to_csv(&client, ["r1.pdf", "r2.pdf"], Some(options), file).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_to_csv<P: AsRef<Path>>(
+    client: &RenamedClient,
+    files: impl IntoIterator<Item = P>,
+    options: Option<ExtractOptions>,
+    writer: impl Write,
+) -> Result<()> {
+    let paths: Vec<PathBuf> = files
+        .into_iter()
+        .map(|p| p.as_ref().to_path_buf())
+        .collect();
+
+    let rows: Vec<(PathBuf, ExtractResult)> = stream::iter(paths)
+        .map(|path| {
+            let options = options.clone();
+            async move {
+                let result = client.extract(&path, options).await?;
+                Ok::<_, RenamedError>((path, result))
+            }
+        })
+        .buffered(BATCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    write_csv(&rows, writer)
+}
+
+/// Writes extraction results as CSV, one row per file.
+fn write_csv(results: &[(PathBuf, ExtractResult)], writer: impl Write) -> Result<()> {
+    let rows: Vec<(&PathBuf, &Map<String, Value>)> = results
+        .iter()
+        .flat_map(|(path, result)| records(result).into_iter().map(move |r| (path, r)))
+        .collect();
+    let columns: BTreeSet<&str> = rows
+        .iter()
+        .flat_map(|(_, record)| record.keys().map(String::as_str))
+        .collect();
+
+    let mut csv = csv::Writer::from_writer(writer);
+    let to_err = |err: csv::Error| RenamedError::from_io(err.into(), "Failed to write CSV");
+
+    csv.write_record(std::iter::once("file").chain(columns.iter().copied()))
+        .map_err(to_err)?;
+
+    for (path, record) in rows {
+        let mut row = vec![path.display().to_string()];
+        row.extend(columns.iter().map(|key| match record.get(*key) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+        }));
+        csv.write_record(&row).map_err(to_err)?;
+    }
+
+    csv.flush()
+        .map_err(|e| RenamedError::from_io(e, "Failed to write CSV"))
+}
+
+/// Returns the records in a result: the object itself, or each object in an array.
+fn records(result: &ExtractResult) -> Vec<&Map<String, Value>> {
+    match &result.data {
+        Value::Object(record) => vec![record],
+        Value::Array(items) => items.iter().filter_map(Value::as_object).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_csv_uses_union_of_keys() {
+        let a: ExtractResult = serde_json::from_str(
+            r#"{"data": {"vendor": "ACME, Inc.", "total": 12.5}, "confidence": 0.9}"#,
+        )
+        .unwrap();
+        let b: ExtractResult = serde_json::from_str(
+            r#"{"data": {"date": "2024-01-31", "total": null}, "confidence": 0.8}"#,
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        write_csv(
+            &[(PathBuf::from("a.pdf"), a), (PathBuf::from("b.pdf"), b)],
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "file,date,total,vendor\n\
+             a.pdf,,12.5,\"ACME, Inc.\"\n\
+             b.pdf,2024-01-31,,\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv_array_data_one_row_per_record() {
+        let items: ExtractResult = serde_json::from_str(
+            r#"{"data": [{"item": "Widget", "qty": 2}, {"item": "Gadget"}], "confidence": 0.9}"#,
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        write_csv(&[(PathBuf::from("order.pdf"), items)], &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "file,item,qty\norder.pdf,Widget,2\norder.pdf,Gadget,\n"
+        );
+    }
+}
@@ -4,20 +4,40 @@
 //! when interacting with the renamed.to API.
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::models::PdfSplitResult;
+
+/// Wait used by [`RenamedError::wait_for_retry()`] when the server gives no hint.
+const DEFAULT_RETRY_WAIT: Duration = Duration::from_secs(1);
+
+/// Status assumed for an error body returned with a success status and no code.
+const SOFT_ERROR_STATUS: u16 = 500;
+
+/// Status assumed for such a body when it names a file problem.
+const SOFT_FILE_ERROR_STATUS: u16 = 422;
+
 /// The main error type for the renamed.to SDK.
 ///
 /// All SDK methods return `Result<T, RenamedError>`, allowing callers to handle
 /// specific error cases appropriately.
+///
+/// New variants may be added in minor releases, so `match` statements need a
+/// wildcard arm.
+///
+/// Messages are `Cow<'static, str>` so fixed messages, such as the default
+/// for a rate limit without a body, are built without allocating.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum RenamedError {
     /// Invalid or missing API key.
     #[error("Authentication error: {message}")]
     Authentication {
         /// Error message describing the authentication failure.
-        message: String,
+        message: Cow<'static, str>,
         /// HTTP status code (typically 401).
         status_code: u16,
     },
@@ -26,64 +46,109 @@ pub enum RenamedError {
     #[error("Insufficient credits: {message}")]
     InsufficientCredits {
         /// Error message describing the credit issue.
-        message: String,
+        message: Cow<'static, str>,
         /// HTTP status code (typically 402).
         status_code: u16,
+        /// Credits currently available on the account (if provided by the API).
+        available: Option<u32>,
+        /// Credits required to complete the operation (if provided by the API).
+        required: Option<u32>,
     },
 
     /// Rate limit exceeded. Wait before retrying.
     #[error("Rate limit exceeded: {message}")]
     RateLimit {
         /// Error message describing the rate limit.
-        message: String,
+        message: Cow<'static, str>,
         /// HTTP status code (typically 429).
         status_code: u16,
         /// Seconds to wait before retrying (if provided by the API).
         retry_after: Option<u32>,
     },
 
+    /// The API is temporarily unavailable, e.g. for planned maintenance.
+    ///
+    /// Returned for a `503` with a `Retry-After` header, and for every
+    /// request the client makes until that time has passed, without
+    /// contacting the API.
+    #[error("Service unavailable: {message}")]
+    ServiceUnavailable {
+        /// Error message describing the outage.
+        message: Cow<'static, str>,
+        /// Seconds until the API is expected to be available again.
+        retry_after: Option<u32>,
+    },
+
     /// Invalid request parameters or payload.
     #[error("Validation error: {message}")]
     Validation {
         /// Error message describing the validation failure.
-        message: String,
+        message: Cow<'static, str>,
         /// HTTP status code (typically 400 or 422).
         status_code: u16,
         /// Additional details about the validation failure.
         details: Option<HashMap<String, serde_json::Value>>,
     },
 
+    /// A value was rejected by the SDK itself, without an HTTP exchange.
+    ///
+    /// Returned for local checks such as parsing an enum name or a page
+    /// range, so unlike [`Validation`](RenamedError::Validation) it carries
+    /// no status code.
+    #[error("Invalid value: {message}")]
+    InvalidValue {
+        /// Error message describing what is wrong with the value.
+        message: Cow<'static, str>,
+        /// The rejected value, if there is a single one.
+        value: Option<String>,
+        /// Additional details about the rejected value.
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
+
     /// Network or connection failure.
     #[error("Network error: {message}")]
     Network {
         /// Error message describing the network failure.
-        message: String,
+        message: Cow<'static, str>,
         /// The underlying reqwest error, if available.
         #[source]
         source: Option<reqwest::Error>,
+        /// Total time spent on the request, including retries.
+        elapsed: Option<Duration>,
     },
 
     /// Request timed out.
     #[error("Timeout error: {message}")]
     Timeout {
         /// Error message describing the timeout.
-        message: String,
+        message: Cow<'static, str>,
+        /// Total time spent on the request, including retries.
+        elapsed: Option<Duration>,
+    },
+
+    /// The operation was cancelled by the caller.
+    #[error("Cancelled: {message}")]
+    Cancelled {
+        /// Error message describing what was cancelled.
+        message: Cow<'static, str>,
     },
 
     /// Async job failed during processing.
     #[error("Job error: {message}")]
     Job {
         /// Error message describing the job failure.
-        message: String,
+        message: Cow<'static, str>,
         /// The job ID if available.
         job_id: Option<String>,
+        /// Documents the job produced before it failed, if the API reported any.
+        partial: Option<Box<PdfSplitResult>>,
     },
 
     /// Generic API error for unexpected status codes.
     #[error("API error ({status_code}): {message}")]
     Api {
         /// Error message from the API.
-        message: String,
+        message: Cow<'static, str>,
         /// HTTP status code.
         status_code: u16,
         /// Error code from the API.
@@ -92,11 +157,25 @@ pub enum RenamedError {
         details: Option<HashMap<String, serde_json::Value>>,
     },
 
+    /// The server could not process the uploaded file itself.
+    ///
+    /// Unlike [`Validation`](RenamedError::Validation), retrying or changing
+    /// the request won't help; in a batch, skip the file and move on.
+    #[error("Unprocessable file ({reason}): {message}")]
+    UnprocessableFile {
+        /// Error message from the API.
+        message: Cow<'static, str>,
+        /// HTTP status code.
+        status_code: u16,
+        /// Why the file could not be processed.
+        reason: FileReason,
+    },
+
     /// File I/O error.
     #[error("File error: {message}")]
     File {
         /// Error message describing the file operation failure.
-        message: String,
+        message: Cow<'static, str>,
         /// The underlying I/O error, if available.
         #[source]
         source: Option<std::io::Error>,
@@ -106,19 +185,84 @@ pub enum RenamedError {
     #[error("Serialization error: {message}")]
     Serialization {
         /// Error message describing the serialization failure.
-        message: String,
+        message: Cow<'static, str>,
         /// The underlying serde_json error, if available.
         #[source]
         source: Option<serde_json::Error>,
     },
 }
 
+/// Why the server could not process an uploaded file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileReason {
+    /// The file is password-protected or encrypted.
+    Encrypted,
+    /// The file is damaged or malformed.
+    Corrupt,
+    /// The file type is not supported.
+    Unsupported,
+}
+
+impl FileReason {
+    /// Maps an API error code to a reason, ignoring case.
+    fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "ENCRYPTED" | "PASSWORD_PROTECTED" => Some(FileReason::Encrypted),
+            "MALFORMED" | "CORRUPT" | "CORRUPTED" => Some(FileReason::Corrupt),
+            "UNSUPPORTED" | "UNSUPPORTED_FILE_TYPE" => Some(FileReason::Unsupported),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FileReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileReason::Encrypted => write!(f, "encrypted"),
+            FileReason::Corrupt => write!(f, "corrupt"),
+            FileReason::Unsupported => write!(f, "unsupported"),
+        }
+    }
+}
+
+/// Message used when an error response carries none of its own.
+///
+/// Common statuses get a static string so building their errors doesn't
+/// allocate; others fall back to a formatted `HTTP <status>`.
+fn default_message(status: u16) -> Cow<'static, str> {
+    let message = match status {
+        400 => "HTTP 400",
+        401 => "HTTP 401",
+        402 => "HTTP 402",
+        403 => "HTTP 403",
+        404 => "HTTP 404",
+        409 => "HTTP 409",
+        413 => "HTTP 413",
+        415 => "HTTP 415",
+        422 => "HTTP 422",
+        429 => "HTTP 429",
+        500 => "HTTP 500",
+        502 => "HTTP 502",
+        503 => "HTTP 503",
+        504 => "HTTP 504",
+        _ => return Cow::Owned(format!("HTTP {}", status)),
+    };
+    Cow::Borrowed(message)
+}
+
 /// API error response structure for deserializing error payloads.
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct ApiErrorResponse {
     pub error: Option<String>,
     #[serde(rename = "retryAfter")]
     pub retry_after: Option<u32>,
+    #[serde(rename = "credits_available", alias = "creditsAvailable")]
+    pub credits_available: Option<u32>,
+    #[serde(rename = "credits_required", alias = "creditsRequired")]
+    pub credits_required: Option<u32>,
+    #[serde(rename = "errorCode", alias = "code")]
+    pub error_code: Option<String>,
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
@@ -126,19 +270,38 @@ pub(crate) struct ApiErrorResponse {
 impl RenamedError {
     /// Creates an appropriate error variant from an HTTP status code and response body.
     pub(crate) fn from_http_status(status: u16, body: Option<&str>) -> Self {
-        let error_response: Option<ApiErrorResponse> =
-            body.and_then(|b| serde_json::from_str(b).ok());
+        // Empty bodies are common for 429s and 5xx; don't pay for a failed parse
+        let mut error_response: Option<ApiErrorResponse> = body
+            .filter(|b| !b.trim().is_empty())
+            .and_then(|b| serde_json::from_str(b).ok());
 
         let message = error_response
-            .as_ref()
-            .and_then(|r| r.error.clone())
-            .unwrap_or_else(|| format!("HTTP {}", status));
+            .as_mut()
+            .and_then(|r| r.error.take())
+            .map_or_else(|| default_message(status), Cow::Owned);
 
         let details = error_response
             .as_ref()
             .map(|r| r.extra.clone())
             .filter(|d| !d.is_empty());
 
+        // File codes only refine client errors that have no variant of their
+        // own, so e.g. a 401 stays an authentication error whatever its code
+        let file_status = (400..500).contains(&status) && !matches!(status, 401 | 402 | 429);
+        let file_reason = error_response
+            .as_ref()
+            .filter(|_| file_status)
+            .and_then(|r| r.error_code.as_deref())
+            .and_then(FileReason::from_code)
+            .or((status == 415).then_some(FileReason::Unsupported));
+        if let Some(reason) = file_reason {
+            return RenamedError::UnprocessableFile {
+                message,
+                status_code: status,
+                reason,
+            };
+        }
+
         match status {
             401 => RenamedError::Authentication {
                 message,
@@ -147,6 +310,8 @@ impl RenamedError {
             402 => RenamedError::InsufficientCredits {
                 message,
                 status_code: status,
+                available: error_response.as_ref().and_then(|r| r.credits_available),
+                required: error_response.as_ref().and_then(|r| r.credits_required),
             },
             400 | 422 => RenamedError::Validation {
                 message,
@@ -167,48 +332,371 @@ impl RenamedError {
         }
     }
 
+    /// Detects an error payload delivered with a success status.
+    ///
+    /// Some proxies answer `200 OK` with a JSON body like `{"error": "..."}`.
+    /// Returns the matching error if `body` is an object with a non-null
+    /// top-level `error`, using its `statusCode` (or numeric `status`) when
+    /// that is an error code. Otherwise it is treated as a `422` if it carries
+    /// a file error code, and as a server error if not.
+    /// Job payloads, which report failures through `error` next to a string
+    /// `status`, are not treated as soft errors unless that status is `"error"`.
+    pub(crate) fn from_soft_error(body: &str) -> Option<Self> {
+        let serde_json::Value::Object(fields) = serde_json::from_str(body).ok()? else {
+            return None;
+        };
+        if fields.get("error").is_none_or(serde_json::Value::is_null)
+            || fields
+                .get("status")
+                .and_then(serde_json::Value::as_str)
+                .is_some_and(|status| status != "error")
+        {
+            return None;
+        }
+
+        let status = ["statusCode", "status"]
+            .iter()
+            .filter_map(|key| fields.get(*key)?.as_u64())
+            .filter_map(|code| u16::try_from(code).ok())
+            .find(|code| *code >= 400)
+            .unwrap_or_else(|| {
+                let file_code = ["errorCode", "code"]
+                    .iter()
+                    .filter_map(|key| fields.get(*key)?.as_str())
+                    .any(|code| FileReason::from_code(code).is_some());
+                if file_code {
+                    SOFT_FILE_ERROR_STATUS
+                } else {
+                    SOFT_ERROR_STATUS
+                }
+            });
+
+        Some(Self::from_http_status(status, Some(body)))
+    }
+
     /// Creates a network error from a reqwest error.
     pub(crate) fn from_reqwest(err: reqwest::Error) -> Self {
         if err.is_timeout() {
             RenamedError::Timeout {
-                message: "Request timed out".to_string(),
+                message: "Request timed out".into(),
+                elapsed: None,
             }
         } else if err.is_connect() {
             RenamedError::Network {
-                message: "Connection failed".to_string(),
+                message: "Connection failed".into(),
                 source: Some(err),
+                elapsed: None,
             }
         } else {
             RenamedError::Network {
-                message: err.to_string(),
+                message: err.to_string().into(),
                 source: Some(err),
+                elapsed: None,
             }
         }
     }
 
+    /// Records the total time spent before a network error or timeout.
+    ///
+    /// Other variants are returned unchanged.
+    pub(crate) fn with_elapsed(mut self, total: Duration) -> Self {
+        if let RenamedError::Network { elapsed, .. } | RenamedError::Timeout { elapsed, .. } =
+            &mut self
+        {
+            *elapsed = Some(total);
+        }
+        self
+    }
+
+    /// Creates the error for an API that is unavailable for another `wait`.
+    pub(crate) fn service_unavailable(wait: Duration) -> Self {
+        let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        RenamedError::ServiceUnavailable {
+            message: format!("API unavailable, retry in {}s", secs).into(),
+            retry_after: Some(u32::try_from(secs).unwrap_or(u32::MAX)),
+        }
+    }
+
+    /// Creates the error for a call whose [deadline](crate::RenamedClient::with_deadline) has passed.
+    pub(crate) fn deadline_exceeded() -> Self {
+        RenamedError::Timeout {
+            message: "Deadline exceeded".into(),
+            elapsed: None,
+        }
+    }
+
     /// Creates a job error.
-    pub(crate) fn job_error(message: impl Into<String>, job_id: Option<String>) -> Self {
+    pub(crate) fn job_error(message: impl Into<Cow<'static, str>>, job_id: Option<String>) -> Self {
         RenamedError::Job {
             message: message.into(),
             job_id,
+            partial: None,
         }
     }
 
     /// Creates a file error from an I/O error.
+    ///
+    /// The message names the common causes (missing file, missing permission,
+    /// directory instead of a file) so batch reports can tell them apart.
     pub(crate) fn from_io(err: std::io::Error, context: impl Into<String>) -> Self {
+        let cause = match err.kind() {
+            std::io::ErrorKind::NotFound => "file not found".to_string(),
+            std::io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+            std::io::ErrorKind::IsADirectory => "is a directory".to_string(),
+            _ => err.to_string(),
+        };
         RenamedError::File {
-            message: context.into(),
+            message: format!("{}: {}", context.into(), cause).into(),
             source: Some(err),
         }
     }
 
+    /// Returns the kind of the underlying I/O error for
+    /// [`File`](RenamedError::File) errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// if let Err(err) = client.rename("scan.pdf", None).await {
+    ///     if err.io_error_kind() == Some(std::io::ErrorKind::NotFound) {
+    ///         eprintln!("scan.pdf no longer exists");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        match self {
+            RenamedError::File {
+                source: Some(source),
+                ..
+            } => Some(source.kind()),
+            _ => None,
+        }
+    }
+
     /// Creates a serialization error.
     pub(crate) fn from_serde(err: serde_json::Error) -> Self {
         RenamedError::Serialization {
-            message: err.to_string(),
+            message: err.to_string().into(),
             source: Some(err),
         }
     }
+
+    /// Returns how long to wait before retrying, or `None` if waiting won't help.
+    fn retry_wait(&self) -> Option<Duration> {
+        match self {
+            RenamedError::RateLimit { retry_after, .. } => Some(
+                retry_after
+                    .map(|secs| Duration::from_secs(u64::from(secs)))
+                    .unwrap_or(DEFAULT_RETRY_WAIT),
+            ),
+            RenamedError::ServiceUnavailable { retry_after, .. } => Some(
+                retry_after
+                    .map(|secs| Duration::from_secs(u64::from(secs)))
+                    .unwrap_or(DEFAULT_RETRY_WAIT),
+            ),
+            RenamedError::Api { status_code, .. } if *status_code >= 500 => {
+                Some(DEFAULT_RETRY_WAIT)
+            }
+            _ => None,
+        }
+    }
+
+    /// Sleeps until it is reasonable to retry the failed request.
+    ///
+    /// For [`RateLimit`](RenamedError::RateLimit) and
+    /// [`ServiceUnavailable`](RenamedError::ServiceUnavailable) errors this
+    /// waits for the server's `retry_after`, and for server errors (5xx) it waits one second,
+    /// which is also the fallback when no `retry_after` was given. Other errors
+    /// return immediately.
+    ///
+    /// Returns `true` if it waited, i.e. if the error is worth retrying.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> Result<(), renamed::RenamedError> {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// let result = loop {
+    ///     match client.rename("invoice.pdf", None).await {
+    ///         Err(err) if err.wait_for_retry().await => continue,
+    ///         other => break other?,
+    ///     }
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_retry(&self) -> bool {
+        match self.retry_wait() {
+            Some(wait) => {
+                tokio::time::sleep(wait).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the error as a JSON object for structured logging.
+    ///
+    /// Every object has `type` (the variant name in snake_case), `message`
+    /// and `status_code` (`null` for errors without an HTTP status), plus the
+    /// variant's own fields. Underlying errors are included as their message
+    /// under `source`, and durations as milliseconds under `elapsed_ms`. The
+    /// [`Serialize`] impl emits the same structure.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() {
+    /// # let client = renamed::RenamedClient::new("api_key");
+    /// if let Err(err) = client.rename("invoice.pdf", None).await {
+    ///     eprintln!("{}", err.to_log_value());
+    /// }
+    /// # }
+    /// ```
+    pub fn to_log_value(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        let (kind, status_code, fields) = match self {
+            RenamedError::Authentication { status_code, .. } => {
+                ("authentication", Some(*status_code), json!({}))
+            }
+            RenamedError::InsufficientCredits {
+                status_code,
+                available,
+                required,
+                ..
+            } => (
+                "insufficient_credits",
+                Some(*status_code),
+                json!({ "available": available, "required": required }),
+            ),
+            RenamedError::RateLimit {
+                status_code,
+                retry_after,
+                ..
+            } => (
+                "rate_limit",
+                Some(*status_code),
+                json!({ "retry_after": retry_after }),
+            ),
+            RenamedError::ServiceUnavailable { retry_after, .. } => (
+                "service_unavailable",
+                None,
+                json!({ "retry_after": retry_after }),
+            ),
+            RenamedError::Validation {
+                status_code,
+                details,
+                ..
+            } => (
+                "validation",
+                Some(*status_code),
+                json!({ "details": details }),
+            ),
+            RenamedError::InvalidValue { value, details, .. } => (
+                "invalid_value",
+                None,
+                json!({ "value": value, "details": details }),
+            ),
+            RenamedError::Network {
+                source, elapsed, ..
+            } => (
+                "network",
+                None,
+                json!({
+                    "source": source.as_ref().map(ToString::to_string),
+                    "elapsed_ms": elapsed.map(|e| e.as_millis()),
+                }),
+            ),
+            RenamedError::Timeout { elapsed, .. } => (
+                "timeout",
+                None,
+                json!({ "elapsed_ms": elapsed.map(|e| e.as_millis()) }),
+            ),
+            RenamedError::Cancelled { .. } => ("cancelled", None, json!({})),
+            RenamedError::Job {
+                job_id, partial, ..
+            } => (
+                "job",
+                None,
+                json!({
+                    "job_id": job_id,
+                    "partial_documents": partial.as_ref().map(|p| p.documents.len()),
+                }),
+            ),
+            RenamedError::Api {
+                status_code,
+                code,
+                details,
+                ..
+            } => (
+                "api",
+                Some(*status_code),
+                json!({ "code": code, "details": details }),
+            ),
+            RenamedError::UnprocessableFile {
+                status_code,
+                reason,
+                ..
+            } => (
+                "unprocessable_file",
+                Some(*status_code),
+                json!({ "reason": reason.to_string() }),
+            ),
+            RenamedError::File { source, .. } => (
+                "file",
+                None,
+                json!({ "source": source.as_ref().map(ToString::to_string) }),
+            ),
+            RenamedError::Serialization { source, .. } => (
+                "serialization",
+                None,
+                json!({ "source": source.as_ref().map(ToString::to_string) }),
+            ),
+        };
+
+        let mut value = json!({
+            "type": kind,
+            "message": self.message(),
+            "status_code": status_code,
+        });
+        if let (Some(object), serde_json::Value::Object(fields)) = (value.as_object_mut(), fields) {
+            object.extend(fields);
+        }
+        value
+    }
+
+    /// Returns the variant's message, without the prefix added by `Display`.
+    fn message(&self) -> &str {
+        match self {
+            RenamedError::Authentication { message, .. }
+            | RenamedError::InsufficientCredits { message, .. }
+            | RenamedError::RateLimit { message, .. }
+            | RenamedError::ServiceUnavailable { message, .. }
+            | RenamedError::Validation { message, .. }
+            | RenamedError::InvalidValue { message, .. }
+            | RenamedError::Network { message, .. }
+            | RenamedError::Timeout { message, .. }
+            | RenamedError::Cancelled { message }
+            | RenamedError::Job { message, .. }
+            | RenamedError::Api { message, .. }
+            | RenamedError::UnprocessableFile { message, .. }
+            | RenamedError::File { message, .. }
+            | RenamedError::Serialization { message, .. } => message,
+        }
+    }
+}
+
+impl Serialize for RenamedError {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        self.to_log_value().serialize(serializer)
+    }
 }
 
 /// Type alias for Results using RenamedError.
@@ -230,6 +718,25 @@ mod tests {
         assert!(matches!(err, RenamedError::InsufficientCredits { .. }));
     }
 
+    #[test]
+    fn test_error_from_402_with_credit_details() {
+        let err = RenamedError::from_http_status(
+            402,
+            Some(r#"{"error": "No credits", "credits_available": 2, "credits_required": 5}"#),
+        );
+        if let RenamedError::InsufficientCredits {
+            available,
+            required,
+            ..
+        } = err
+        {
+            assert_eq!(available, Some(2));
+            assert_eq!(required, Some(5));
+        } else {
+            panic!("Expected InsufficientCredits error");
+        }
+    }
+
     #[test]
     fn test_error_from_429() {
         let err = RenamedError::from_http_status(
@@ -242,4 +749,205 @@ mod tests {
             panic!("Expected RateLimit error");
         }
     }
+
+    #[test]
+    fn test_default_messages_are_static() {
+        match RenamedError::from_http_status(429, Some("")) {
+            RenamedError::RateLimit { message, .. } => {
+                assert!(matches!(message, Cow::Borrowed("HTTP 429")));
+            }
+            other => panic!("Expected RateLimit error, got {:?}", other),
+        }
+
+        match RenamedError::from_http_status(418, None) {
+            RenamedError::Api { message, .. } => assert_eq!(message, "HTTP 418"),
+            other => panic!("Expected Api error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_soft_error_from_success_body() {
+        let err = RenamedError::from_soft_error(r#"{"error": "Upstream unavailable"}"#).unwrap();
+        match err {
+            RenamedError::Api {
+                message,
+                status_code,
+                ..
+            } => {
+                assert_eq!(message, "Upstream unavailable");
+                assert_eq!(status_code, 500);
+            }
+            other => panic!("Expected Api error, got {:?}", other),
+        }
+
+        let err =
+            RenamedError::from_soft_error(r#"{"error": "Invalid API key", "statusCode": 401}"#);
+        assert!(matches!(err, Some(RenamedError::Authentication { .. })));
+
+        // Regular payloads, including failed job statuses, are left alone
+        assert!(RenamedError::from_soft_error(r#"{"id": "u_1", "error": null}"#).is_none());
+        assert!(RenamedError::from_soft_error(
+            r#"{"jobId": "job_1", "status": "failed", "error": "Corrupt PDF"}"#
+        )
+        .is_none());
+        assert!(RenamedError::from_soft_error("[]").is_none());
+        assert!(RenamedError::from_soft_error("not json").is_none());
+    }
+
+    #[test]
+    fn test_unprocessable_file_from_error_code() {
+        let err = RenamedError::from_http_status(
+            422,
+            Some(r#"{"error": "PDF is password protected", "errorCode": "ENCRYPTED"}"#),
+        );
+        match err {
+            RenamedError::UnprocessableFile {
+                reason, message, ..
+            } => {
+                assert_eq!(reason, FileReason::Encrypted);
+                assert_eq!(message, "PDF is password protected");
+            }
+            other => panic!("Expected UnprocessableFile error, got {:?}", other),
+        }
+
+        let err =
+            RenamedError::from_http_status(400, Some(r#"{"error": "Bad", "code": "malformed"}"#));
+        assert!(matches!(
+            err,
+            RenamedError::UnprocessableFile {
+                reason: FileReason::Corrupt,
+                ..
+            }
+        ));
+
+        let err = RenamedError::from_http_status(415, Some(r#"{"error": "Unsupported type"}"#));
+        assert!(matches!(
+            err,
+            RenamedError::UnprocessableFile {
+                reason: FileReason::Unsupported,
+                ..
+            }
+        ));
+
+        // Rename responses report file errors with a 200 and "status": "error"
+        let err = RenamedError::from_soft_error(
+            r#"{"status": "error", "error": "Cannot read PDF", "errorCode": "MALFORMED"}"#,
+        );
+        assert!(matches!(
+            err,
+            Some(RenamedError::UnprocessableFile {
+                reason: FileReason::Corrupt,
+                ..
+            })
+        ));
+
+        let err = RenamedError::from_http_status(400, Some(r#"{"error": "Missing file"}"#));
+        assert!(matches!(err, RenamedError::Validation { .. }));
+    }
+
+    #[test]
+    fn test_file_codes_do_not_override_specific_statuses() {
+        let body = Some(r#"{"error": "Rejected", "errorCode": "ENCRYPTED"}"#);
+
+        assert!(matches!(
+            RenamedError::from_http_status(401, body),
+            RenamedError::Authentication { .. }
+        ));
+        assert!(matches!(
+            RenamedError::from_http_status(402, body),
+            RenamedError::InsufficientCredits { .. }
+        ));
+        assert!(matches!(
+            RenamedError::from_http_status(429, body),
+            RenamedError::RateLimit { .. }
+        ));
+        assert!(matches!(
+            RenamedError::from_http_status(500, body),
+            RenamedError::Api { .. }
+        ));
+    }
+
+    #[test]
+    fn test_to_log_value() {
+        let err = RenamedError::from_http_status(
+            402,
+            Some(r#"{"error": "No credits", "credits_available": 2, "credits_required": 5}"#),
+        );
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "type": "insufficient_credits",
+                "message": "No credits",
+                "status_code": 402,
+                "available": 2,
+                "required": 5,
+            })
+        );
+
+        let err = RenamedError::Timeout {
+            message: "Request timed out".into(),
+            elapsed: Some(Duration::from_millis(1500)),
+        };
+        assert_eq!(
+            err.to_log_value(),
+            serde_json::json!({
+                "type": "timeout",
+                "message": "Request timed out",
+                "status_code": null,
+                "elapsed_ms": 1500,
+            })
+        );
+
+        let err = RenamedError::from_serde(serde_json::from_str::<u8>("x").unwrap_err());
+        let value = err.to_log_value();
+        assert_eq!(value["type"], "serialization");
+        assert!(value["source"].is_string());
+    }
+
+    #[test]
+    fn test_file_error_names_io_cause() {
+        let err = RenamedError::from_io(
+            std::io::Error::from(std::io::ErrorKind::NotFound),
+            "Failed to read file: a.pdf",
+        );
+        assert_eq!(
+            err.to_string(),
+            "File error: Failed to read file: a.pdf: file not found"
+        );
+        assert_eq!(err.io_error_kind(), Some(std::io::ErrorKind::NotFound));
+
+        let err = RenamedError::from_io(
+            std::io::Error::from(std::io::ErrorKind::IsADirectory),
+            "Failed to read file: docs",
+        );
+        assert_eq!(
+            err.to_string(),
+            "File error: Failed to read file: docs: is a directory"
+        );
+        assert_eq!(
+            RenamedError::from_http_status(401, None).io_error_kind(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_retry_wait() {
+        let err = RenamedError::from_http_status(429, Some(r#"{"retryAfter": 5}"#));
+        assert_eq!(err.retry_wait(), Some(Duration::from_secs(5)));
+
+        let err = RenamedError::from_http_status(429, None);
+        assert_eq!(err.retry_wait(), Some(DEFAULT_RETRY_WAIT));
+
+        let err = RenamedError::from_http_status(503, None);
+        assert_eq!(err.retry_wait(), Some(DEFAULT_RETRY_WAIT));
+
+        let err = RenamedError::from_http_status(401, None);
+        assert_eq!(err.retry_wait(), None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_retry_returns_immediately_when_not_retryable() {
+        let err = RenamedError::from_http_status(400, None);
+        assert!(!err.wait_for_retry().await);
+    }
 }
@@ -17,7 +17,7 @@
 //!
 //!     // Check your credits
 //!     let user = client.get_user().await?;
-//!     println!("Credits remaining: {}", user.credits.unwrap_or(0));
+//!     println!("Credits remaining: {}", user.credits.unwrap_or_default());
 //!
 //!     // Rename a file using AI
 //!     let result = client.rename("invoice.pdf", None).await?;
@@ -109,18 +109,44 @@
 #![deny(unsafe_code)]
 
 mod async_job;
+mod batch;
+mod circuit_breaker;
 mod client;
+#[cfg(feature = "csv")]
+mod csv_export;
 mod error;
+mod maintenance;
+mod metrics;
+#[cfg(test)]
+mod mock_server;
 mod models;
+mod pdf;
+mod rate_limit;
+mod retry;
+mod retry_budget;
+mod template;
+mod upload_budget;
+
+/// Version of this SDK, also sent in the `User-Agent` header of every request.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Re-export main types at crate root for convenience
-pub use async_job::{AsyncJob, ProgressCallback};
-pub use client::{RenamedClient, RenamedClientBuilder};
-pub use error::{RenamedError, Result};
+pub use async_job::{cancel_all, AsyncJob, JobUpdates, ProgressCallback};
+pub use batch::{apply_plan, BatchOptions, BatchResult, RenamePlan};
+pub use client::{cancellable, RenamedClient, RenamedClientBuilder};
+#[cfg(feature = "csv")]
+pub use csv_export::extract_to_csv;
+pub use error::{FileReason, RenamedError, Result};
+pub use futures::future::AbortHandle;
+pub use metrics::RequestMetrics;
 pub use models::{
-    ExtractOptions, ExtractResult, JobStatus, JobStatusResponse, PdfSplitOptions, PdfSplitResult,
-    RenameOptions, RenameResult, SplitDocument, SplitMode, Team, User,
+    Case, Credits, DocumentType, DownloadOptions, ExtractOptions, ExtractResult, ExtractedInfo,
+    JobStatus, JobStatusResponse, PdfSplitOptions, PdfSplitResult, RenameCandidate, RenameOptions,
+    RenameResult, SplitDocument, SplitMode, Team, User,
 };
+pub use rate_limit::RateLimitInfo;
+pub use retry::{DefaultRetryStrategy, RetryPolicy, RetryStrategy};
+pub use template::render_template;
 
 /// Prelude module for convenient imports.
 ///
@@ -132,7 +158,7 @@ pub mod prelude {
     pub use crate::client::RenamedClient;
     pub use crate::error::{RenamedError, Result};
     pub use crate::models::{
-        ExtractOptions, ExtractResult, JobStatus, PdfSplitOptions, PdfSplitResult, RenameOptions,
-        RenameResult, SplitDocument, SplitMode, User,
+        Credits, DownloadOptions, ExtractOptions, ExtractResult, JobStatus, PdfSplitOptions,
+        PdfSplitResult, RenameOptions, RenameResult, SplitDocument, SplitMode, User,
     };
 }
@@ -0,0 +1,211 @@
+//! Client-wide back-off during API maintenance.
+//!
+//! During planned maintenance the API answers `503 Service Unavailable` with a
+//! `Retry-After` header. The first such response closes a gate shared by every
+//! clone of the client, and requests fail fast with
+//! [`RenamedError::ServiceUnavailable`](crate::RenamedError::ServiceUnavailable)
+//! until the announced time instead of each one discovering the outage.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Longest maintenance window honoured; longer announcements are clamped.
+pub(crate) const MAX_RETRY_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The end of the announced maintenance window, if any.
+#[derive(Debug, Default)]
+pub(crate) struct MaintenanceGate {
+    /// When the API is expected back, if a maintenance window is announced.
+    until: Mutex<Option<Instant>>,
+}
+
+impl MaintenanceGate {
+    /// Creates an open gate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fails requests for the next `wait`, extending any current window.
+    ///
+    /// `wait` is clamped to [`MAX_RETRY_AFTER`].
+    pub fn close_for(&self, wait: Duration) {
+        let Some(until) = Instant::now().checked_add(wait.min(MAX_RETRY_AFTER)) else {
+            return;
+        };
+        let mut current = self.until.lock().unwrap_or_else(|e| e.into_inner());
+        // `None` orders before any `Some`, so an open gate is always closed
+        if *current < Some(until) {
+            *current = Some(until);
+        }
+    }
+
+    /// Returns how long requests must still wait, or `None` if the gate is open.
+    pub fn remaining(&self) -> Option<Duration> {
+        let until = (*self.until.lock().unwrap_or_else(|e| e.into_inner()))?;
+        let remaining = until.saturating_duration_since(Instant::now());
+        (!remaining.is_zero()).then_some(remaining)
+    }
+}
+
+/// Parses a `Retry-After` header value into the time left to wait.
+///
+/// Accepts both forms the header allows: a number of seconds, and an HTTP
+/// date such as `Wed, 21 Oct 2015 07:28:00 GMT`. A date in the past yields
+/// `None`, and waits longer than [`MAX_RETRY_AFTER`] are clamped to it.
+pub(crate) fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    let wait = if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        // Too many digits for a u64 is still just a very long wait
+        Duration::from_secs(value.parse().unwrap_or(u64::MAX))
+    } else {
+        parse_http_date(value)?.duration_since(now).ok()?
+    };
+    Some(wait.min(MAX_RETRY_AFTER))
+}
+
+/// Parses an IMF-fixdate (`Wed, 21 Oct 2015 07:28:00 GMT`).
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let mut parts = value.split_whitespace();
+    let _weekday = parts.next()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = match parts.next()? {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year: u32 = parts.next()?.parse().ok()?;
+    let mut time = parts.next()?.split(':').map(|n| n.parse::<u32>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if parts.next()? != "GMT" || time.next().is_some() {
+        return None;
+    }
+    utc_time(year, month, day, hour, minute, second)
+}
+
+/// Converts a UTC calendar date and time into a [`SystemTime`].
+///
+/// Returns `None` for fields out of range, such as a 31st of April or an hour
+/// of 24, and for dates before 1970 or after 9999.
+pub(crate) fn utc_time(
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+) -> Option<SystemTime> {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1970..=9999).contains(&year)
+        || !(1..=days_in_month).contains(&day)
+        || hour > 23
+        || minute > 59
+        // 60 allows for a leap second
+        || second > 60
+    {
+        return None;
+    }
+
+    // Days since the epoch for a proleptic Gregorian date, with March as the
+    // first month so the leap day falls at the end of the year
+    let (year, month, day) = (u64::from(year), u64::from(month), u64::from(day));
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era_day = 365 * year + year / 4 - year / 100 + year / 400 + (153 * month + 2) / 5 + day - 1;
+    let days = era_day.checked_sub(719_468)?;
+
+    let secs = days
+        .checked_mul(86_400)?
+        .checked_add(u64::from(hour * 3_600 + minute * 60 + second))?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_400);
+
+        assert_eq!(
+            parse_retry_after(" 120 ", now),
+            Some(Duration::from_secs(120))
+        );
+        // 2015-10-21 07:28:00 UTC is 1_445_412_480
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(80))
+        );
+        assert_eq!(
+            parse_retry_after("Thu, 01 Jan 1970 00:00:00 GMT", now),
+            None
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(
+            parse_retry_after("Sat, 31 Apr 2015 07:28:00 GMT", now),
+            None
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 24:00:00 GMT", now),
+            None
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 99999 07:28:00 GMT", now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_clamps_absurd_values() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_400);
+
+        assert_eq!(
+            parse_retry_after("99999999999999999999", now),
+            Some(MAX_RETRY_AFTER)
+        );
+        assert_eq!(
+            parse_retry_after("Fri, 31 Dec 9999 23:59:59 GMT", now),
+            Some(MAX_RETRY_AFTER)
+        );
+
+        let gate = MaintenanceGate::new();
+        gate.close_for(Duration::MAX);
+        assert!(gate.remaining().is_some_and(|left| left <= MAX_RETRY_AFTER));
+    }
+
+    #[test]
+    fn test_gate_closes_and_reopens() {
+        let gate = MaintenanceGate::new();
+        assert_eq!(gate.remaining(), None);
+
+        gate.close_for(Duration::from_millis(50));
+        assert!(gate.remaining().is_some());
+
+        // A shorter window never cuts an announced one short
+        gate.close_for(Duration::ZERO);
+        assert!(gate.remaining().is_some());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(gate.remaining(), None);
+    }
+}
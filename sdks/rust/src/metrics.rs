@@ -0,0 +1,52 @@
+//! Per-request metrics reported to an observer hook.
+//!
+//! Register a hook with
+//! [`RenamedClientBuilder::on_complete()`](crate::RenamedClientBuilder::on_complete)
+//! to receive a [`RequestMetrics`] after every API request finishes, whether it
+//! succeeded or failed.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Metrics describing a completed API request, including its retries.
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// HTTP method (e.g. `"POST"`).
+    pub method: String,
+
+    /// Request URL.
+    pub url: String,
+
+    /// HTTP status of the final attempt, or `None` if no response was received.
+    pub status_code: Option<u16>,
+
+    /// Number of retries performed before the final attempt.
+    pub retries: u32,
+
+    /// Total time spent on the request, including retries and backoff.
+    pub elapsed: Duration,
+
+    /// Whether the request ultimately succeeded.
+    pub success: bool,
+}
+
+/// A shareable observer invoked with [`RequestMetrics`] after each request.
+#[derive(Clone)]
+pub(crate) struct CompletionHook(Arc<dyn Fn(&RequestMetrics) + Send + Sync>);
+
+impl CompletionHook {
+    pub fn new(hook: impl Fn(&RequestMetrics) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    pub fn call(&self, metrics: &RequestMetrics) {
+        (self.0)(metrics)
+    }
+}
+
+impl fmt::Debug for CompletionHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CompletionHook")
+    }
+}
@@ -0,0 +1,214 @@
+//! Scripted HTTP server for unit tests.
+//!
+//! Each accepted connection gets the next scripted response and is closed,
+//! so a test can line up the exact sequence of replies a client will see.
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// One scripted reply.
+#[derive(Debug, Clone)]
+pub(crate) struct MockResponse {
+    status: u16,
+    body: String,
+    headers: Vec<(&'static str, String)>,
+    delay: Duration,
+    truncated: bool,
+}
+
+impl MockResponse {
+    /// Creates a JSON response with the given status.
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+            headers: Vec::new(),
+            delay: Duration::ZERO,
+            truncated: false,
+        }
+    }
+
+    /// Adds a response header.
+    pub fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Closes the connection before the whole declared body was sent.
+    pub fn truncated(mut self) -> Self {
+        self.truncated = true;
+        self
+    }
+
+    /// Waits `delay` after reading the request before answering it.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// A bound server that hasn't started answering yet.
+///
+/// Binding first lets responses refer to the server's own address, e.g. in
+/// a job's status URL.
+pub(crate) struct MockServer {
+    listener: TcpListener,
+    addr: SocketAddr,
+    hold_unanswered: bool,
+}
+
+impl MockServer {
+    /// Binds to a free local port.
+    pub async fn bind() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        Self {
+            listener,
+            addr,
+            hold_unanswered: false,
+        }
+    }
+
+    /// Returns the address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Keeps connections beyond the scripted responses open without
+    /// answering, instead of refusing them.
+    pub fn hold_unanswered(mut self) -> Self {
+        self.hold_unanswered = true;
+        self
+    }
+
+    /// Answers one connection with each response, in order.
+    ///
+    /// Once the responses run out the listener is closed, unless
+    /// [`hold_unanswered()`](Self::hold_unanswered) was set.
+    pub fn serve(self, responses: Vec<MockResponse>) -> MockHandle {
+        let handle = MockHandle {
+            addr: self.addr,
+            requests: Arc::default(),
+            in_flight: Arc::default(),
+        };
+        let requests = Arc::clone(&handle.requests);
+        let in_flight = Arc::clone(&handle.in_flight);
+
+        tokio::spawn(async move {
+            let mut responses = responses.into_iter();
+            let mut held = Vec::new();
+            while responses.len() > 0 || self.hold_unanswered {
+                let Ok((socket, _)) = self.listener.accept().await else {
+                    break;
+                };
+                match responses.next() {
+                    Some(response) => {
+                        tokio::spawn(answer(
+                            socket,
+                            response,
+                            Arc::clone(&requests),
+                            Arc::clone(&in_flight),
+                        ));
+                    }
+                    None => held.push(socket),
+                }
+            }
+        });
+        handle
+    }
+}
+
+/// A running server.
+#[derive(Debug, Clone)]
+pub(crate) struct MockHandle {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<String>>>,
+    in_flight: Arc<InFlight>,
+}
+
+impl MockHandle {
+    /// Returns the address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the raw requests read so far, in the order they arrived.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Returns the most requests that were being answered at the same time.
+    pub fn max_in_flight(&self) -> usize {
+        self.in_flight.max.load(Ordering::SeqCst)
+    }
+}
+
+/// Requests read but not yet answered, and the most there have been at once.
+#[derive(Debug, Default)]
+struct InFlight {
+    current: AtomicUsize,
+    max: AtomicUsize,
+}
+
+/// Reads one request from `socket` and writes `response` to it.
+async fn answer(
+    mut socket: TcpStream,
+    response: MockResponse,
+    requests: Arc<Mutex<Vec<String>>>,
+    in_flight: Arc<InFlight>,
+) {
+    let request = read_request(&mut socket).await;
+    requests.lock().unwrap().push(request);
+    let current = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+    in_flight.max.fetch_max(current, Ordering::SeqCst);
+    tokio::time::sleep(response.delay).await;
+    in_flight.current.fetch_sub(1, Ordering::SeqCst);
+
+    let headers: String = response
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    let reply = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        headers,
+        response.body.len() + usize::from(response.truncated),
+        response.body
+    );
+    // The client may have given up on the request already
+    let _ = socket.write_all(reply.as_bytes()).await;
+}
+
+/// Reads a request's head and, if it declares a `Content-Length`, its body.
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    while let Ok(read) = socket.read(&mut chunk).await {
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&chunk[..read]);
+
+        let Some(head_len) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8_lossy(&request[..head_len]).to_ascii_lowercase();
+        let body_len = head
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .and_then(|len| len.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        if request.len() >= head_len + 4 + body_len {
+            break;
+        }
+    }
+
+    String::from_utf8_lossy(&request).into_owned()
+}
@@ -3,7 +3,28 @@
 //! This module contains all request/response types used by the SDK.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::error::{RenamedError, Result};
+
+/// Field-by-field merging of per-call options with client-wide defaults.
+pub(crate) trait MergeOptions: Clone {
+    /// Fills every unset field from `defaults`; fields set on `self` win.
+    fn merge(self, defaults: &Self) -> Self;
+}
+
+/// Combines optional per-call options with optional client defaults.
+pub(crate) fn merge_options<T: MergeOptions>(
+    options: Option<T>,
+    defaults: Option<&T>,
+) -> Option<T> {
+    match (options, defaults) {
+        (Some(options), Some(defaults)) => Some(options.merge(defaults)),
+        (options, defaults) => options.or_else(|| defaults.cloned()),
+    }
+}
 
 // ============================================================================
 // Rename Types
@@ -12,6 +33,7 @@ use std::collections::HashMap;
 /// Result of a rename operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct RenameResult {
     /// The original filename that was uploaded.
     pub original_filename: String,
@@ -26,6 +48,277 @@ pub struct RenameResult {
     /// Confidence score (0.0 - 1.0) of the suggestion.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f64>,
+
+    /// Information the AI extracted while naming the document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extracted_info: Option<ExtractedInfo>,
+
+    /// Other candidate names the API considered, if it returned any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<RenameCandidate>,
+}
+
+/// A candidate filename with its confidence score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RenameCandidate {
+    /// Candidate filename.
+    pub filename: String,
+
+    /// Confidence score (0.0 - 1.0) of the candidate.
+    pub confidence: f64,
+}
+
+impl RenameResult {
+    /// Returns the detected category of the document, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::DocumentType;
+    ///
+    /// # let json = r#"{"originalFilename": "a.pdf", "suggestedFilename": "b.pdf", "extractedInfo": {"document_type": "invoice"}}"#;
+    /// # let result: renamed::RenameResult = serde_json::from_str(json).unwrap();
+    /// match result.document_type() {
+    ///     Some(DocumentType::Invoice) => println!("Route to accounts payable"),
+    ///     Some(other) => println!("Filed as {}", other),
+    ///     None => println!("Unknown category"),
+    /// }
+    /// ```
+    pub fn document_type(&self) -> Option<&DocumentType> {
+        self.extracted_info.as_ref()?.document_type.as_ref()
+    }
+
+    /// Returns the most confident name scoring at least `threshold`.
+    ///
+    /// Considers the suggested filename and any [`alternatives`](Self::alternatives).
+    /// A suggestion without a confidence score never qualifies. On a tie the
+    /// suggested filename wins.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let json = r#"{"originalFilename": "a.pdf", "suggestedFilename": "Invoice.pdf", "confidence": 0.6,
+    /// #     "alternatives": [{"filename": "ACME_Invoice.pdf", "confidence": 0.9}]}"#;
+    /// # let result: renamed::RenameResult = serde_json::from_str(json).unwrap();
+    /// assert_eq!(result.best_above(0.8), Some("ACME_Invoice.pdf"));
+    /// assert_eq!(result.best_above(0.95), None);
+    /// ```
+    pub fn best_above(&self, threshold: f64) -> Option<&str> {
+        let suggested = self
+            .confidence
+            .map(|confidence| (self.suggested_filename.as_str(), confidence));
+        let alternatives = self
+            .alternatives
+            .iter()
+            .map(|c| (c.filename.as_str(), c.confidence));
+
+        suggested
+            .into_iter()
+            .chain(alternatives)
+            .filter(|(_, confidence)| *confidence >= threshold)
+            .fold(None, |best: Option<(&str, f64)>, candidate| match best {
+                Some(best) if best.1 >= candidate.1 => Some(best),
+                _ => Some(candidate),
+            })
+            .map(|(filename, _)| filename)
+    }
+
+    /// Returns the suggested folder path split into its segments.
+    ///
+    /// Splits on `/`, ignoring empty segments from leading, trailing, or
+    /// repeated slashes. Returns an empty vector if there is no folder path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let json = r#"{"originalFilename": "a.pdf", "suggestedFilename": "b.pdf", "folderPath": "/Invoices/2024/"}"#;
+    /// # let result: renamed::RenameResult = serde_json::from_str(json).unwrap();
+    /// assert_eq!(result.folder_segments(), vec!["Invoices", "2024"]);
+    /// ```
+    pub fn folder_segments(&self) -> Vec<&str> {
+        self.folder_path
+            .as_deref()
+            .map(|path| path.split('/').filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the suggested folder as a relative [`PathBuf`].
+    ///
+    /// `.` and `..` segments are dropped so the result can be safely joined onto
+    /// an output directory. Returns `None` if there is no usable folder path.
+    pub fn folder_pathbuf(&self) -> Option<PathBuf> {
+        self.folder_path.as_deref().and_then(relative_folder)
+    }
+
+    /// Returns the suggested filename reformatted to a naming convention.
+    ///
+    /// With a `separator`, words separated by spaces, `_` or `-` are joined
+    /// with it; with `None`, the existing separators are kept. `case` is then
+    /// applied. The extension is left unchanged. Path separators (`/`, `\`)
+    /// are ignored as `separator` so the result stays a single filename.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::Case;
+    ///
+    /// # let json = r#"{"originalFilename": "a.pdf", "suggestedFilename": "2024-03-01 Acme Invoice.pdf"}"#;
+    /// # let result: renamed::RenameResult = serde_json::from_str(json).unwrap();
+    /// assert_eq!(result.formatted(Case::Lower, Some('_')), "2024_03_01_acme_invoice.pdf");
+    /// assert_eq!(result.formatted(Case::Preserve, Some('-')), "2024-03-01-Acme-Invoice.pdf");
+    /// ```
+    pub fn formatted(&self, case: Case, separator: Option<char>) -> String {
+        let name = self.suggested_filename.as_str();
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() && !extension.contains(' ') => {
+                (stem, &name[stem.len()..])
+            }
+            _ => (name, ""),
+        };
+
+        let stem = match separator.filter(|sep| !matches!(sep, '/' | '\\')) {
+            Some(separator) => stem
+                .split(|c: char| c.is_whitespace() || c == '_' || c == '-' || c == separator)
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(&separator.to_string()),
+            None => stem.to_string(),
+        };
+
+        let stem = match case {
+            Case::Preserve => stem,
+            Case::Lower => stem.to_lowercase(),
+            Case::Upper => stem.to_uppercase(),
+        };
+        stem + extension
+    }
+}
+
+/// Letter case for a formatted filename. See [`RenameResult::formatted()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Case {
+    /// Keep the letter case as suggested.
+    #[default]
+    Preserve,
+    /// Lowercase every letter.
+    Lower,
+    /// Uppercase every letter.
+    Upper,
+}
+
+/// Information extracted from a document during a rename.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ExtractedInfo {
+    /// Document date, as written by the API.
+    #[serde(default)]
+    pub date: Option<String>,
+
+    /// Issuer of the document (e.g. a vendor or bank).
+    #[serde(default)]
+    pub issuer: Option<String>,
+
+    /// Invoice or document number.
+    #[serde(default)]
+    pub invoice_number: Option<String>,
+
+    /// Category of the document.
+    #[serde(default)]
+    pub document_type: Option<DocumentType>,
+}
+
+/// Category the AI assigned to a document.
+///
+/// Categories this SDK doesn't know yet are kept as [`Other`](Self::Other)
+/// rather than failing deserialization, so new server-side categories don't
+/// break existing code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum DocumentType {
+    /// An invoice or bill.
+    Invoice,
+    /// A receipt for a completed payment.
+    Receipt,
+    /// A contract or agreement.
+    Contract,
+    /// A bank or account statement.
+    BankStatement,
+    /// A tax return, assessment or other tax form.
+    TaxDocument,
+    /// A payslip or salary statement.
+    Payslip,
+    /// A letter or other correspondence.
+    Letter,
+    /// A category not known to this SDK, as sent by the API.
+    Other(String),
+}
+
+impl std::fmt::Display for DocumentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentType::Invoice => write!(f, "invoice"),
+            DocumentType::Receipt => write!(f, "receipt"),
+            DocumentType::Contract => write!(f, "contract"),
+            DocumentType::BankStatement => write!(f, "bank_statement"),
+            DocumentType::TaxDocument => write!(f, "tax_document"),
+            DocumentType::Payslip => write!(f, "payslip"),
+            DocumentType::Letter => write!(f, "letter"),
+            DocumentType::Other(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl std::str::FromStr for DocumentType {
+    type Err = std::convert::Infallible;
+
+    /// Parses a category case-insensitively, treating spaces and hyphens like
+    /// underscores. Unknown categories become [`DocumentType::Other`].
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase().replace([' ', '-'], "_");
+        Ok(match normalized.as_str() {
+            "invoice" => DocumentType::Invoice,
+            "receipt" => DocumentType::Receipt,
+            "contract" => DocumentType::Contract,
+            "bank_statement" => DocumentType::BankStatement,
+            "tax_document" => DocumentType::TaxDocument,
+            "payslip" => DocumentType::Payslip,
+            "letter" => DocumentType::Letter,
+            _ => DocumentType::Other(s.to_string()),
+        })
+    }
+}
+
+impl From<String> for DocumentType {
+    fn from(value: String) -> Self {
+        let Ok(document_type) = value.parse();
+        document_type
+    }
+}
+
+impl From<DocumentType> for String {
+    fn from(value: DocumentType) -> Self {
+        match value {
+            DocumentType::Other(value) => value,
+            known => known.to_string(),
+        }
+    }
+}
+
+/// Converts a `/`-separated folder path into a relative [`PathBuf`].
+///
+/// Empty, `.`, and `..` segments are dropped. Returns `None` if nothing remains.
+pub(crate) fn relative_folder(folder_path: &str) -> Option<PathBuf> {
+    let path: PathBuf = folder_path
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != "." && *s != "..")
+        .collect();
+
+    if path.as_os_str().is_empty() {
+        None
+    } else {
+        Some(path)
+    }
 }
 
 /// Options for the rename operation.
@@ -33,6 +326,18 @@ pub struct RenameResult {
 pub struct RenameOptions {
     /// Custom template for filename generation.
     pub template: Option<String>,
+
+    /// Send the file's current name as a hint for the suggestion.
+    pub original_name_as_hint: Option<bool>,
+
+    /// Ask the server to use the document's embedded metadata as context.
+    pub include_metadata: Option<bool>,
+
+    /// Letter case applied to the suggested filename.
+    pub case: Option<Case>,
+
+    /// Separator placed between words of the suggested filename.
+    pub separator: Option<char>,
 }
 
 impl RenameOptions {
@@ -46,6 +351,69 @@ impl RenameOptions {
         self.template = Some(template.into());
         self
     }
+
+    /// Sends the file's existing name as a `hint` so the AI can consider it.
+    ///
+    /// Useful when the original name carries information, such as a
+    /// scanner-assigned date. Off by default, in which case only the file
+    /// contents are used.
+    pub fn use_original_name_as_hint(mut self, enabled: bool) -> Self {
+        self.original_name_as_hint = Some(enabled);
+        self
+    }
+
+    /// Asks the server to consider the document's embedded metadata.
+    ///
+    /// PDFs often carry an author, title and creation date that help name
+    /// them, e.g. a scan with a meaningless name but a useful title. Off by
+    /// default, in which case only the file contents are used.
+    pub fn include_metadata(mut self, enabled: bool) -> Self {
+        self.include_metadata = Some(enabled);
+        self
+    }
+
+    /// Sets the letter case of the suggested filename.
+    ///
+    /// The API has no formatting options, so the suggestion is reformatted
+    /// locally with [`RenameResult::formatted()`] before it is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::{Case, RenameOptions};
+    ///
+    /// // snake_case names, e.g. "2024_03_01_acme_invoice.pdf"
+    /// let options = RenameOptions::new()
+    ///     .with_case(Case::Lower)
+    ///     .with_separator('_');
+    /// ```
+    pub fn with_case(mut self, case: Case) -> Self {
+        self.case = Some(case);
+        self
+    }
+
+    /// Sets the separator placed between words of the suggested filename.
+    ///
+    /// Spaces, `_` and `-` in the suggestion are replaced by `separator`.
+    /// Like [`with_case()`](Self::with_case), this is applied locally.
+    pub fn with_separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+}
+
+impl MergeOptions for RenameOptions {
+    fn merge(self, defaults: &Self) -> Self {
+        Self {
+            template: self.template.or_else(|| defaults.template.clone()),
+            original_name_as_hint: self
+                .original_name_as_hint
+                .or(defaults.original_name_as_hint),
+            include_metadata: self.include_metadata.or(defaults.include_metadata),
+            case: self.case.or(defaults.case),
+            separator: self.separator.or(defaults.separator),
+        }
+    }
 }
 
 // ============================================================================
@@ -75,6 +443,61 @@ impl std::fmt::Display for SplitMode {
     }
 }
 
+impl std::str::FromStr for SplitMode {
+    type Err = RenamedError;
+
+    /// Parses a split mode case-insensitively, e.g. from a CLI flag.
+    ///
+    /// Accepts the same names as [`Display`](std::fmt::Display): `auto`,
+    /// `pages` and `blank`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(SplitMode::Auto),
+            "pages" => Ok(SplitMode::Pages),
+            "blank" => Ok(SplitMode::Blank),
+            _ => Err(unknown_variant(
+                "split mode",
+                s,
+                &["auto", "pages", "blank"],
+            )),
+        }
+    }
+}
+
+impl TryFrom<&str> for SplitMode {
+    type Error = RenamedError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+/// Error for a string that names none of an enum's variants.
+fn unknown_variant(kind: &str, value: &str, expected: &[&str]) -> RenamedError {
+    RenamedError::InvalidValue {
+        message: format!(
+            "Unknown {} '{}', expected one of: {}",
+            kind,
+            value,
+            expected.join(", ")
+        )
+        .into(),
+        value: Some(value.to_string()),
+        details: None,
+    }
+}
+
+/// Deserializes an optional split mode, mapping modes this SDK doesn't know to `None`.
+///
+/// Keeps results readable when the server starts reporting new strategies.
+fn lenient_split_mode<'de, D>(deserializer: D) -> std::result::Result<Option<SplitMode>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|v| serde_json::from_value(v).ok()))
+}
+
 /// Options for PDF split operation.
 #[derive(Debug, Clone, Default)]
 pub struct PdfSplitOptions {
@@ -83,6 +506,15 @@ pub struct PdfSplitOptions {
 
     /// Number of pages per split (for `Pages` mode).
     pub pages_per_split: Option<u32>,
+
+    /// Whether to ask for small outputs inline in the job result.
+    pub inline_output: Option<bool>,
+
+    /// Interval at which the returned job polls for status.
+    pub poll_interval: Option<Duration>,
+
+    /// How long the returned job polls before giving up.
+    pub poll_timeout: Option<Duration>,
 }
 
 impl PdfSplitOptions {
@@ -102,11 +534,53 @@ impl PdfSplitOptions {
         self.pages_per_split = Some(pages);
         self
     }
+
+    /// Asks the API to return split documents inline in the job result.
+    ///
+    /// Inlined documents carry their bytes in [`SplitDocument::content`], saving
+    /// a download request each. The server only inlines small outputs; larger
+    /// ones still have to be fetched from their download URL.
+    pub fn with_inline_output(mut self, inline: bool) -> Self {
+        self.inline_output = Some(inline);
+        self
+    }
+
+    /// Sets the poll interval of the returned [`AsyncJob`](crate::AsyncJob).
+    ///
+    /// Same as calling [`AsyncJob::with_poll_interval()`](crate::AsyncJob::with_poll_interval)
+    /// on the job.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets how long the returned [`AsyncJob`](crate::AsyncJob) polls before
+    /// giving up.
+    ///
+    /// Same as calling [`AsyncJob::with_poll_timeout()`](crate::AsyncJob::with_poll_timeout)
+    /// on the job.
+    pub fn with_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.poll_timeout = Some(timeout);
+        self
+    }
+}
+
+impl MergeOptions for PdfSplitOptions {
+    fn merge(self, defaults: &Self) -> Self {
+        Self {
+            mode: self.mode.or(defaults.mode),
+            pages_per_split: self.pages_per_split.or(defaults.pages_per_split),
+            inline_output: self.inline_output.or(defaults.inline_output),
+            poll_interval: self.poll_interval.or(defaults.poll_interval),
+            poll_timeout: self.poll_timeout.or(defaults.poll_timeout),
+        }
+    }
 }
 
 /// A single document from a PDF split operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct SplitDocument {
     /// Document index (0-based).
     pub index: u32,
@@ -122,11 +596,190 @@ pub struct SplitDocument {
 
     /// Size in bytes.
     pub size: i64,
+
+    /// Document bytes, if the API returned them inline.
+    ///
+    /// Only set for small outputs of splits requested with
+    /// [`PdfSplitOptions::with_inline_output()`]; otherwise download the
+    /// document from [`download_url`](Self::download_url).
+    #[serde(
+        default,
+        with = "base64_content",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub content: Option<Vec<u8>>,
+}
+
+/// Serde adapter for optional base64-encoded bytes.
+mod base64_content {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        content: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match content {
+            Some(bytes) => serializer.serialize_str(&STANDARD.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|encoded| STANDARD.decode(encoded).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+impl SplitDocument {
+    /// Parses [`pages`](Self::pages) into inclusive 1-based `(start, end)` pages.
+    ///
+    /// Accepts a single page (`"5"`) or a range (`"1-3"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::InvalidValue`], holding the `pages` string, if
+    /// it is not a page number or range, a page is `0`, or the range ends
+    /// before it starts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let json = r#"{"index": 0, "filename": "a.pdf", "pages": "2-4", "downloadUrl": "", "size": 0}"#;
+    /// # let doc: renamed::SplitDocument = serde_json::from_str(json).unwrap();
+    /// assert_eq!(doc.page_range().unwrap(), (2, 4));
+    /// assert_eq!(doc.page_count(), 3);
+    /// ```
+    pub fn page_range(&self) -> Result<(u32, u32)> {
+        let invalid = |reason: &str| RenamedError::InvalidValue {
+            message: format!("Invalid page range {:?}: {}", self.pages, reason).into(),
+            value: Some(self.pages.clone()),
+            details: None,
+        };
+        let parse = |page: &str| {
+            page.trim()
+                .parse::<u32>()
+                .map_err(|_| invalid("expected a page number or a range like \"1-3\""))
+        };
+
+        let (start, end) = match self.pages.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => {
+                let page = parse(&self.pages)?;
+                (page, page)
+            }
+        };
+
+        if start == 0 {
+            return Err(invalid("pages are numbered from 1"));
+        }
+        if end < start {
+            return Err(invalid("range ends before it starts"));
+        }
+        Ok((start, end))
+    }
+
+    /// Returns the number of pages in this document, or `0` if
+    /// [`pages`](Self::pages) cannot be parsed.
+    pub fn page_count(&self) -> u32 {
+        self.page_range()
+            .map(|(start, end)| end - start + 1)
+            .unwrap_or(0)
+    }
+
+    /// Returns a name that is safe to save this document under.
+    ///
+    /// Prefers [`filename`](Self::filename). If it is empty, the name is
+    /// derived from [`download_url`](Self::download_url): a `filename` query
+    /// parameter if present, else the last path segment, percent-decoded.
+    /// Either way, only the final path component is kept and characters that
+    /// aren't valid in filenames on Windows, macOS or Linux are replaced with
+    /// `_`. Falls back to `document_<index>.pdf`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let json = r#"{"index": 0, "filename": "", "pages": "1",
+    /// #     "downloadUrl": "https://cdn.example.com/d/abc?filename=Invoice%20March%3A2024.pdf", "size": 0}"#;
+    /// # let doc: renamed::SplitDocument = serde_json::from_str(json).unwrap();
+    /// assert_eq!(doc.local_filename(), "Invoice March_2024.pdf");
+    /// ```
+    pub fn local_filename(&self) -> String {
+        let name = if self.filename.trim().is_empty() {
+            filename_from_url(&self.download_url)
+        } else {
+            self.filename.clone()
+        };
+        sanitize_filename(&name).unwrap_or_else(|| format!("document_{}.pdf", self.index))
+    }
+}
+
+/// Derives a percent-decoded filename from a URL's `filename` query
+/// parameter or, failing that, its last path segment.
+fn filename_from_url(url: &str) -> String {
+    let without_fragment = url.split('#').next().unwrap_or_default();
+    let (path, query) = without_fragment
+        .split_once('?')
+        .unwrap_or((without_fragment, ""));
+
+    let from_query = query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == "filename").then(|| percent_decode(&value.replace('+', " ")))
+    });
+    from_query.unwrap_or_else(|| percent_decode(path.rsplit('/').next().unwrap_or_default()))
+}
+
+/// Decodes `%XX` escapes, leaving malformed escapes as they are.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Keeps the final path component of `name` and replaces characters that
+/// are invalid in filenames on common platforms.
+///
+/// Returns `None` if nothing usable remains.
+fn sanitize_filename(name: &str) -> Option<String> {
+    let last = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let sanitized: String = last
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Windows drops trailing dots and spaces, and "." / ".." aren't files
+    let sanitized = sanitized.trim().trim_end_matches('.');
+    (!sanitized.is_empty()).then(|| sanitized.to_string())
 }
 
 /// Result of a PDF split operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct PdfSplitResult {
     /// The original filename.
     pub original_filename: String,
@@ -136,6 +789,175 @@ pub struct PdfSplitResult {
 
     /// Total number of pages in the original document.
     pub total_pages: u32,
+
+    /// Credits actually charged for the split (if reported by the API).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credits_used: Option<u32>,
+
+    /// Split mode the server actually applied, e.g. the strategy it chose for
+    /// [`SplitMode::Auto`]. `None` if not reported or not known to this SDK.
+    #[serde(
+        default,
+        deserialize_with = "lenient_split_mode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mode_used: Option<SplitMode>,
+}
+
+impl PdfSplitResult {
+    /// Verifies that the documents cover every page exactly once.
+    ///
+    /// Parses each document's page range and checks that together they tile
+    /// `1..=total_pages` with no gaps or overlaps.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::InvalidValue`] if a document's page range is
+    /// malformed, with the range as its `value`. Also returns it if pages are
+    /// missing, covered more than once, or beyond `total_pages`; the error's
+    /// `details` then hold `gaps`, `overlaps`, and `out_of_range` as lists of
+    /// inclusive `[start, end]` ranges.
+    pub fn verify_coverage(&self) -> Result<()> {
+        let mut ranges = self
+            .documents
+            .iter()
+            .map(SplitDocument::page_range)
+            .collect::<Result<Vec<_>>>()?;
+        ranges.sort_unstable();
+
+        let mut gaps = Vec::new();
+        let mut overlaps = Vec::new();
+        let mut out_of_range = Vec::new();
+        // First page not yet covered by an earlier range
+        let mut next: u64 = 1;
+
+        for (start, end) in ranges {
+            let (start, end) = (u64::from(start), u64::from(end));
+            if start > next {
+                gaps.push([next, start - 1]);
+            } else if start < next {
+                overlaps.push([start, end.min(next - 1)]);
+            }
+            if end > u64::from(self.total_pages) {
+                out_of_range.push([start.max(u64::from(self.total_pages) + 1), end]);
+            }
+            next = next.max(end + 1);
+        }
+        if next <= u64::from(self.total_pages) {
+            gaps.push([next, u64::from(self.total_pages)]);
+        }
+
+        if gaps.is_empty() && overlaps.is_empty() && out_of_range.is_empty() {
+            return Ok(());
+        }
+
+        let describe = |label: &str, ranges: &[[u64; 2]]| {
+            (!ranges.is_empty()).then(|| {
+                let list = ranges
+                    .iter()
+                    .map(|[s, e]| {
+                        if s == e {
+                            s.to_string()
+                        } else {
+                            format!("{}-{}", s, e)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{} {}", label, list)
+            })
+        };
+        let summary = [
+            describe("missing pages", &gaps),
+            describe("overlapping pages", &overlaps),
+            describe("pages beyond the document", &out_of_range),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("; ");
+
+        Err(RenamedError::InvalidValue {
+            message: format!(
+                "Split documents do not cover pages 1-{}: {}",
+                self.total_pages, summary
+            )
+            .into(),
+            value: None,
+            details: Some(HashMap::from([
+                ("gaps".to_string(), serde_json::json!(gaps)),
+                ("overlaps".to_string(), serde_json::json!(overlaps)),
+                ("out_of_range".to_string(), serde_json::json!(out_of_range)),
+            ])),
+        })
+    }
+
+    /// Returns true if two or more documents would be saved under the same
+    /// [`local_filename()`](SplitDocument::local_filename).
+    ///
+    /// Names that differ only in characters replaced during sanitizing, such
+    /// as `a:b.pdf` and `a_b.pdf`, collide too. Writing such documents into one
+    /// directory would overwrite files; use
+    /// [`deduplicated_filenames()`](Self::deduplicated_filenames) instead.
+    pub fn has_filename_collisions(&self) -> bool {
+        let mut seen = HashSet::new();
+        !self
+            .documents
+            .iter()
+            .all(|doc| seen.insert(doc.local_filename()))
+    }
+
+    /// Returns the documents' local filenames, made unique by appending counters.
+    ///
+    /// Starts from each document's [`local_filename()`](SplitDocument::local_filename).
+    /// The first occurrence of a name is kept as-is; later duplicates get
+    /// `_2`, `_3`, ... inserted before the extension, skipping any name that
+    /// is already in use. Names are returned in document order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let json = r#"{"originalFilename": "scan.pdf", "totalPages": 2, "documents": [
+    /// #     {"index": 0, "filename": "invoice.pdf", "pages": "1", "downloadUrl": "", "size": 0},
+    /// #     {"index": 1, "filename": "invoice.pdf", "pages": "2", "downloadUrl": "", "size": 0}]}"#;
+    /// # let result: renamed::PdfSplitResult = serde_json::from_str(json).unwrap();
+    /// assert!(result.has_filename_collisions());
+    /// assert_eq!(result.deduplicated_filenames(), vec!["invoice.pdf", "invoice_2.pdf"]);
+    /// ```
+    pub fn deduplicated_filenames(&self) -> Vec<String> {
+        deduplicate_filenames(self.documents.iter().map(SplitDocument::local_filename))
+    }
+}
+
+/// Makes filenames unique by appending counters to later duplicates.
+pub(crate) fn deduplicate_filenames(names: impl IntoIterator<Item = String>) -> Vec<String> {
+    let names: Vec<String> = names.into_iter().collect();
+    let original: HashSet<String> = names.iter().cloned().collect();
+    let mut used = HashSet::new();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let unique = if used.contains(&name) {
+                (2..)
+                    .map(|n| with_counter(&name, n))
+                    .find(|candidate| !original.contains(candidate) && !used.contains(candidate))
+                    .expect("counter space exhausted")
+            } else {
+                name
+            };
+            used.insert(unique.clone());
+            unique
+        })
+        .collect()
+}
+
+/// Inserts `_n` before the extension of `name`.
+fn with_counter(name: &str, n: u32) -> String {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => format!("{}_{}{}", &name[..dot], n, &name[dot..]),
+        _ => format!("{}_{}", name, n),
+    }
 }
 
 // ============================================================================
@@ -179,9 +1001,38 @@ impl std::fmt::Display for JobStatus {
     }
 }
 
+impl std::str::FromStr for JobStatus {
+    type Err = RenamedError;
+
+    /// Parses a job status case-insensitively, accepting the same names as
+    /// [`Display`](std::fmt::Display).
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pending" => Ok(JobStatus::Pending),
+            "processing" => Ok(JobStatus::Processing),
+            "completed" => Ok(JobStatus::Completed),
+            "failed" => Ok(JobStatus::Failed),
+            _ => Err(unknown_variant(
+                "job status",
+                s,
+                &["pending", "processing", "completed", "failed"],
+            )),
+        }
+    }
+}
+
+impl TryFrom<&str> for JobStatus {
+    type Error = RenamedError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
 /// Response from the job status endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct JobStatusResponse {
     /// Unique job identifier.
     pub job_id: String,
@@ -193,6 +1044,11 @@ pub struct JobStatusResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub progress: Option<u8>,
 
+    /// Position in the processing queue while the job is pending, where `1`
+    /// is next (if reported by the API).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<u32>,
+
     /// Error message if job failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -200,6 +1056,31 @@ pub struct JobStatusResponse {
     /// Result data when job is completed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<PdfSplitResult>,
+
+    /// URL of the result payload, when a completed job references its result
+    /// instead of embedding it. [`AsyncJob::wait()`](crate::AsyncJob::wait)
+    /// downloads it automatically.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_url: Option<String>,
+
+    /// Credits charged for the job (if reported by the API).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credits_used: Option<u32>,
+
+    /// Split mode the server applied (if reported by the API).
+    #[serde(
+        default,
+        deserialize_with = "lenient_split_mode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mode_used: Option<SplitMode>,
+
+    /// Fields the SDK doesn't model yet, keyed by their name in the payload.
+    ///
+    /// Lets new server-side fields (e.g. an estimated time remaining) be read
+    /// before this type gains a field for them.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Initial response from PDF split endpoint containing the status URL.
@@ -221,6 +1102,19 @@ pub struct ExtractOptions {
 
     /// Natural language description of what to extract.
     pub prompt: Option<String>,
+
+    /// Language for the extracted output (e.g. `"en"`), independent of the
+    /// prompt's language.
+    pub output_language: Option<String>,
+
+    /// Background information about the document, separate from the prompt.
+    pub context: Option<String>,
+
+    /// Validate the extracted data against `schema` before returning it.
+    ///
+    /// Only takes effect with the `jsonschema` feature; without it the flag
+    /// is ignored.
+    pub validate: Option<bool>,
 }
 
 impl ExtractOptions {
@@ -235,22 +1129,345 @@ impl ExtractOptions {
         self
     }
 
+    /// Sets the extraction schema from a JSON string.
+    ///
+    /// Useful when the schema is loaded from a file or configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::Serialization`] if `json` is not a valid JSON object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::ExtractOptions;
+    ///
+    /// let options = ExtractOptions::new()
+    ///     .with_schema_json(r#"{"invoice_number": "string", "total": "number"}"#)?;
+    /// # Ok::<(), renamed::RenamedError>(())
+    /// ```
+    pub fn with_schema_json(mut self, json: &str) -> Result<Self> {
+        let schema = serde_json::from_str(json).map_err(RenamedError::from_serde)?;
+        self.schema = Some(schema);
+        Ok(self)
+    }
+
+    /// Sets the extraction schema from a JSON value.
+    ///
+    /// Convenient for schemas built with [`serde_json::json!`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::Serialization`] if `schema` is not a JSON object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::ExtractOptions;
+    /// use serde_json::json;
+    ///
+    /// let options = ExtractOptions::new().with_schema_value(json!({
+    ///     "type": "object",
+    ///     "properties": {"total": {"type": "number"}}
+    /// }))?;
+    /// # Ok::<(), renamed::RenamedError>(())
+    /// ```
+    pub fn with_schema_value(mut self, schema: serde_json::Value) -> Result<Self> {
+        match schema {
+            serde_json::Value::Object(schema) => {
+                self.schema = Some(schema.into_iter().collect());
+                Ok(self)
+            }
+            other => Err(RenamedError::Serialization {
+                message: format!("Extraction schema must be a JSON object, got: {}", other).into(),
+                source: None,
+            }),
+        }
+    }
+
     /// Sets a natural language prompt describing what to extract.
     pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
         self.prompt = Some(prompt.into());
         self
     }
+
+    /// Sets the language of the extracted output, as a language tag like `"en"`.
+    ///
+    /// The prompt can be written in any language; this only controls the
+    /// output, e.g. to get English field names and values for a fixed schema
+    /// from a Spanish prompt.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::ExtractOptions;
+    ///
+    /// let options = ExtractOptions::new()
+    ///     .with_prompt("Extrae el número de factura y el total")
+    ///     .with_output_language("en");
+    /// ```
+    pub fn with_output_language(mut self, language: impl Into<String>) -> Self {
+        self.output_language = Some(language.into());
+        self
+    }
+
+    /// Sets background information the model should know about the document.
+    ///
+    /// Unlike the prompt, which says what to extract, context describes facts
+    /// that aren't in the file itself, such as who sent it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use renamed::ExtractOptions;
+    ///
+    /// let options = ExtractOptions::new()
+    ///     .with_prompt("Extract the fiscal quarter and total")
+    ///     .with_context("Sent by vendor ACME. Our fiscal year starts in July.");
+    /// ```
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Enables validating the extracted data against the schema.
+    ///
+    /// When enabled and a schema is set, extraction fails with
+    /// [`RenamedError::InvalidValue`] if the returned data does not conform.
+    /// See [`ExtractResult::validate_against()`] for how the schema is applied.
+    #[cfg(feature = "jsonschema")]
+    pub fn with_validation(mut self, enabled: bool) -> Self {
+        self.validate = Some(enabled);
+        self
+    }
+}
+
+impl MergeOptions for ExtractOptions {
+    fn merge(self, defaults: &Self) -> Self {
+        Self {
+            schema: self.schema.or_else(|| defaults.schema.clone()),
+            prompt: self.prompt.or_else(|| defaults.prompt.clone()),
+            output_language: self
+                .output_language
+                .or_else(|| defaults.output_language.clone()),
+            context: self.context.or_else(|| defaults.context.clone()),
+            validate: self.validate.or(defaults.validate),
+        }
+    }
 }
 
 /// Result of an extract operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct ExtractResult {
     /// The extracted data matching the schema.
-    pub data: HashMap<String, serde_json::Value>,
+    ///
+    /// Usually a JSON object, but may be an array for documents that yield a
+    /// list of records (e.g. line items). See [`as_object()`](Self::as_object)
+    /// and [`as_array()`](Self::as_array).
+    pub data: serde_json::Value,
 
     /// Confidence score (0.0 - 1.0).
     pub confidence: f64,
+
+    /// Credits actually charged for the extraction (if reported by the API).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credits_used: Option<u32>,
+}
+
+impl ExtractResult {
+    /// Returns the extracted data as an object, if it is one.
+    pub fn as_object(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.data.as_object()
+    }
+
+    /// Returns the extracted data as an array, if it is one.
+    pub fn as_array(&self) -> Option<&Vec<serde_json::Value>> {
+        self.data.as_array()
+    }
+
+    /// Returns the extracted value for `key`.
+    ///
+    /// Returns `None` if the key is missing or the data is not an object.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.data.get(key)
+    }
+
+    /// Returns the extracted value for `key` as a string slice.
+    ///
+    /// Returns `None` if the key is missing or the value is not a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.data.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Returns the extracted value for `key` as an `f64`.
+    ///
+    /// Returns `None` if the key is missing or the value is not a number.
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.data.get(key).and_then(|v| v.as_f64())
+    }
+
+    /// Returns the extracted value for `key` as an `i64`.
+    ///
+    /// Returns `None` if the key is missing or the value is not an integer.
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.data.get(key).and_then(|v| v.as_i64())
+    }
+
+    /// Returns the extracted value for `key` as a `bool`.
+    ///
+    /// Returns `None` if the key is missing or the value is not a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.data.get(key).and_then(|v| v.as_bool())
+    }
+
+    /// Validates the extracted data against a JSON schema.
+    ///
+    /// A schema with a top-level `type`, `properties` or `$schema` key is used
+    /// as a JSON Schema. Any other schema is read as the field descriptions
+    /// sent with [`ExtractOptions::with_schema()`]. Fields described by a JSON
+    /// type name, such as `"number"` or `"string"`, must have that type when
+    /// present. Fields with free-text descriptions are not checked. For stricter
+    /// checks, such as required fields, pass a full JSON Schema, for example via
+    /// [`ExtractOptions::with_schema_value()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenamedError::InvalidValue`] if the schema is invalid or the data
+    /// does not conform to it. The error's `details` contain an `errors` array
+    /// with the offending `field` (a JSON pointer) and a `message` for each
+    /// violation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let json = r#"{"data": {"total": "n/a"}, "confidence": 0.9}"#;
+    /// # let result: renamed::ExtractResult = serde_json::from_str(json).unwrap();
+    /// let schema = serde_json::from_str(r#"{
+    ///     "type": "object",
+    ///     "properties": {"total": {"type": "number"}},
+    ///     "required": ["total"]
+    /// }"#).unwrap();
+    ///
+    /// assert!(result.validate_against(&schema).is_err());
+    /// ```
+    #[cfg(feature = "jsonschema")]
+    pub fn validate_against(&self, schema: &HashMap<String, serde_json::Value>) -> Result<()> {
+        let schema = json_schema_for(schema);
+        let validator =
+            jsonschema::validator_for(&schema).map_err(|e| RenamedError::InvalidValue {
+                message: format!("Invalid extraction schema: {}", e).into(),
+                value: None,
+                details: None,
+            })?;
+
+        let errors: Vec<(String, String)> = validator
+            .iter_errors(&self.data)
+            .map(|e| (e.instance_path.as_str().to_string(), e.to_string()))
+            .collect();
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        let summary = errors
+            .iter()
+            .map(|(field, message)| {
+                format!(
+                    "{}: {}",
+                    if field.is_empty() { "/" } else { field },
+                    message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        let errors = errors
+            .into_iter()
+            .map(|(field, message)| serde_json::json!({ "field": field, "message": message }))
+            .collect();
+
+        Err(RenamedError::InvalidValue {
+            message: format!("Extracted data does not match schema: {}", summary).into(),
+            value: None,
+            details: Some(HashMap::from([(
+                "errors".to_string(),
+                serde_json::Value::Array(errors),
+            )])),
+        })
+    }
+}
+
+/// Returns an extraction schema as a JSON Schema document.
+///
+/// See [`ExtractResult::validate_against()`] for how field descriptions are
+/// converted.
+#[cfg(feature = "jsonschema")]
+fn json_schema_for(schema: &HashMap<String, serde_json::Value>) -> serde_json::Value {
+    const JSON_TYPES: [&str; 7] = [
+        "string", "number", "integer", "boolean", "array", "object", "null",
+    ];
+
+    if ["type", "properties", "$schema"]
+        .iter()
+        .any(|key| schema.contains_key(*key))
+    {
+        return serde_json::Value::Object(schema.clone().into_iter().collect());
+    }
+
+    let properties: serde_json::Map<String, serde_json::Value> = schema
+        .iter()
+        .map(|(field, description)| {
+            let kind = description.as_str().map(str::to_ascii_lowercase);
+            let property = match kind {
+                Some(kind) if JSON_TYPES.contains(&kind.as_str()) => {
+                    serde_json::json!({ "type": kind })
+                }
+                _ => serde_json::json!({}),
+            };
+            (field.clone(), property)
+        })
+        .collect();
+    serde_json::json!({ "type": "object", "properties": properties })
+}
+
+// ============================================================================
+// Download Types
+// ============================================================================
+
+/// Options for downloading a file to disk.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    /// Resume from the bytes already present at the destination path.
+    ///
+    /// When enabled, the download sends a `Range` header and appends to the
+    /// existing file. Failures the client's retry strategy accepts, such as
+    /// connection drops, are retried from the last written byte.
+    /// If the server does not answer with `206 Partial Content`, the file is
+    /// downloaded again from the start.
+    pub resume: bool,
+
+    /// Expected final size in bytes, verified once the download finishes.
+    pub expected_size: Option<u64>,
+}
+
+impl DownloadOptions {
+    /// Creates new download options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables resuming from a partially downloaded file.
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Sets the expected final size in bytes (e.g., [`SplitDocument::size`]).
+    pub fn with_expected_size(mut self, size: u64) -> Self {
+        self.expected_size = Some(size);
+        self
+    }
 }
 
 // ============================================================================
@@ -259,6 +1476,7 @@ pub struct ExtractResult {
 
 /// Team information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Team {
     /// Team ID.
     pub id: String,
@@ -267,8 +1485,59 @@ pub struct Team {
     pub name: String,
 }
 
+/// A credit balance.
+///
+/// Serializes as a bare number, matching the API's wire format.
+///
+/// # Example
+///
+/// ```rust
+/// use renamed::Credits;
+///
+/// let balance = Credits(10);
+/// assert!(balance.is_sufficient_for(4));
+/// assert_eq!(balance.saturating_sub(4), Credits(6));
+/// assert_eq!(balance.to_string(), "10");
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Credits(pub i32);
+
+impl Credits {
+    /// Returns the number of credits.
+    pub fn get(self) -> i32 {
+        self.0
+    }
+
+    /// Returns true if the balance covers an operation costing `cost` credits.
+    pub fn is_sufficient_for(self, cost: u32) -> bool {
+        i64::from(self.0) >= i64::from(cost)
+    }
+
+    /// Subtracts `cost` credits, saturating at `i32::MIN` instead of overflowing.
+    pub fn saturating_sub(self, cost: u32) -> Credits {
+        let cost = i32::try_from(cost).unwrap_or(i32::MAX);
+        Credits(self.0.saturating_sub(cost))
+    }
+}
+
+impl From<i32> for Credits {
+    fn from(credits: i32) -> Self {
+        Credits(credits)
+    }
+}
+
+impl std::fmt::Display for Credits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// User profile information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct User {
     /// User ID.
     pub id: String,
@@ -282,7 +1551,7 @@ pub struct User {
 
     /// Available credits.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub credits: Option<i32>,
+    pub credits: Option<Credits>,
 
     /// Team information (if applicable).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -293,6 +1562,82 @@ pub struct User {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_job_status_keeps_unknown_fields() {
+        let status: JobStatusResponse = serde_json::from_str(
+            r#"{"jobId": "job_1", "status": "processing", "progress": 40, "queuePosition": 3, "etaSeconds": 12}"#,
+        )
+        .unwrap();
+        assert_eq!(status.progress, Some(40));
+        assert_eq!(status.queue_position, Some(3));
+        assert_eq!(status.extra.len(), 1);
+        assert_eq!(status.extra["etaSeconds"], 12);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["etaSeconds"], 12);
+    }
+
+    #[test]
+    fn test_rename_result_formatted() {
+        let result: RenameResult = serde_json::from_str(
+            r#"{"originalFilename": "a.pdf", "suggestedFilename": "Acme  Invoice_March-2024.PDF"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            result.formatted(Case::Lower, Some('-')),
+            "acme-invoice-march-2024.PDF"
+        );
+        assert_eq!(
+            result.formatted(Case::Upper, None),
+            "ACME  INVOICE_MARCH-2024.PDF"
+        );
+        assert_eq!(
+            result.formatted(Case::Preserve, Some('/')),
+            "Acme  Invoice_March-2024.PDF"
+        );
+
+        let result: RenameResult = serde_json::from_str(
+            r#"{"originalFilename": "a", "suggestedFilename": "Report v2. final"}"#,
+        )
+        .unwrap();
+        assert_eq!(result.formatted(Case::Lower, Some('_')), "report_v2._final");
+    }
+
+    #[test]
+    fn test_local_filename() {
+        let doc = |filename: &str, download_url: &str| SplitDocument {
+            index: 2,
+            filename: filename.to_string(),
+            pages: "1".to_string(),
+            download_url: download_url.to_string(),
+            size: 0,
+            content: None,
+        };
+
+        assert_eq!(doc("../a:b.pdf", "").local_filename(), "a_b.pdf");
+        assert_eq!(
+            doc("", "https://cdn.example.com/d/My%20Scan.pdf?sig=x%2F").local_filename(),
+            "My Scan.pdf"
+        );
+        assert_eq!(
+            doc(
+                "",
+                "https://cdn.example.com/d/abc?filename=Q1+report%2Fv2.pdf"
+            )
+            .local_filename(),
+            "v2.pdf"
+        );
+        assert_eq!(
+            doc("", "https://cdn.example.com/d/100%zz").local_filename(),
+            "100%zz"
+        );
+        assert_eq!(
+            doc("", "https://cdn.example.com/").local_filename(),
+            "document_2.pdf"
+        );
+        assert_eq!(doc("..", "").local_filename(), "document_2.pdf");
+    }
+
     #[test]
     fn test_rename_result_deserialization() {
         let json = r#"{
@@ -309,6 +1654,234 @@ mod tests {
         assert_eq!(result.confidence, Some(0.95));
     }
 
+    #[test]
+    fn test_rename_result_folder_helpers() {
+        let mut result = RenameResult {
+            original_filename: "scan.pdf".to_string(),
+            suggested_filename: "Invoice.pdf".to_string(),
+            folder_path: Some("/Invoices//2024/".to_string()),
+            confidence: None,
+            extracted_info: None,
+            alternatives: Vec::new(),
+        };
+        assert_eq!(result.folder_segments(), vec!["Invoices", "2024"]);
+        assert_eq!(
+            result.folder_pathbuf(),
+            Some(PathBuf::from("Invoices").join("2024"))
+        );
+
+        result.folder_path = Some("../..".to_string());
+        assert_eq!(result.folder_pathbuf(), None);
+
+        result.folder_path = None;
+        assert!(result.folder_segments().is_empty());
+        assert_eq!(result.folder_pathbuf(), None);
+    }
+
+    #[test]
+    fn test_document_type_round_trip() {
+        let json = r#"{
+            "originalFilename": "scan.pdf",
+            "suggestedFilename": "Statement.pdf",
+            "extractedInfo": {"date": "2024-01-31", "issuer": "ACME Bank", "invoice_number": null, "document_type": "Bank Statement"}
+        }"#;
+        let result: RenameResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.document_type(), Some(&DocumentType::BankStatement));
+
+        let other: DocumentType = serde_json::from_str(r#""Medical Record""#).unwrap();
+        assert_eq!(other, DocumentType::Other("Medical Record".to_string()));
+        assert_eq!(other.to_string(), "Medical Record");
+        assert_eq!(
+            serde_json::to_string(&DocumentType::TaxDocument).unwrap(),
+            r#""tax_document""#
+        );
+        assert_eq!(
+            "RECEIPT".parse::<DocumentType>().unwrap(),
+            DocumentType::Receipt
+        );
+    }
+
+    #[test]
+    fn test_rename_result_best_above() {
+        let json = r#"{"originalFilename": "a.pdf", "suggestedFilename": "Invoice.pdf", "confidence": 0.8}"#;
+        let mut result: RenameResult = serde_json::from_str(json).unwrap();
+        assert!(result.alternatives.is_empty());
+        assert_eq!(result.best_above(0.5), Some("Invoice.pdf"));
+
+        result.alternatives = vec![
+            RenameCandidate {
+                filename: "Invoice_ACME.pdf".to_string(),
+                confidence: 0.8,
+            },
+            RenameCandidate {
+                filename: "Receipt.pdf".to_string(),
+                confidence: 0.3,
+            },
+        ];
+        // Ties go to the suggestion
+        assert_eq!(result.best_above(0.5), Some("Invoice.pdf"));
+
+        result.confidence = None;
+        assert_eq!(result.best_above(0.5), Some("Invoice_ACME.pdf"));
+        assert_eq!(result.best_above(0.9), None);
+    }
+
+    #[test]
+    fn test_user_credits_wire_format() {
+        let json = r#"{"id": "u_1", "email": "a@example.com", "credits": 5}"#;
+        let user: User = serde_json::from_str(json).unwrap();
+        let credits = user.credits.unwrap();
+
+        assert_eq!(credits, Credits(5));
+        assert!(credits.is_sufficient_for(5));
+        assert!(!credits.is_sufficient_for(6));
+        assert_eq!(credits.saturating_sub(8), Credits(-3));
+        assert_eq!(credits.saturating_sub(u32::MAX), Credits(5 - i32::MAX));
+        assert_eq!(
+            serde_json::to_value(&user).unwrap()["credits"],
+            serde_json::json!(5)
+        );
+    }
+
+    #[test]
+    fn test_extract_result_typed_accessors() {
+        let json = r#"{
+            "data": {"vendor": "ACME", "total": 42.5, "items": 3, "paid": true},
+            "confidence": 0.9
+        }"#;
+
+        let result: ExtractResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.get_str("vendor"), Some("ACME"));
+        assert_eq!(result.get_f64("total"), Some(42.5));
+        assert_eq!(result.get_i64("items"), Some(3));
+        assert_eq!(result.get_bool("paid"), Some(true));
+        assert_eq!(result.get_str("total"), None);
+        assert_eq!(result.get_i64("missing"), None);
+    }
+
+    #[test]
+    fn test_extract_result_array_data() {
+        let json = r#"{
+            "data": [{"item": "Widget", "qty": 2}, {"item": "Gadget", "qty": 1}],
+            "confidence": 0.8
+        }"#;
+
+        let result: ExtractResult = serde_json::from_str(json).unwrap();
+        assert!(result.as_object().is_none());
+        assert_eq!(result.as_array().map(Vec::len), Some(2));
+        assert_eq!(result.data[1]["item"], "Gadget");
+        assert_eq!(result.get_str("item"), None);
+    }
+
+    #[test]
+    fn test_job_status_credits_used() {
+        let json = r#"{
+            "jobId": "job_1",
+            "status": "completed",
+            "creditsUsed": 4,
+            "result": {
+                "originalFilename": "scan.pdf",
+                "documents": [],
+                "totalPages": 4,
+                "creditsUsed": 4
+            }
+        }"#;
+
+        let status: JobStatusResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(status.credits_used, Some(4));
+        assert_eq!(status.result.unwrap().credits_used, Some(4));
+    }
+
+    #[test]
+    fn test_pdf_split_result_mode_used() {
+        let result: PdfSplitResult = serde_json::from_str(
+            r#"{"originalFilename": "scan.pdf", "documents": [], "totalPages": 4, "modeUsed": "blank"}"#,
+        )
+        .unwrap();
+        assert_eq!(result.mode_used, Some(SplitMode::Blank));
+
+        // Unknown strategies don't break deserialization
+        let result: PdfSplitResult = serde_json::from_str(
+            r#"{"originalFilename": "scan.pdf", "documents": [], "totalPages": 4, "modeUsed": "layout"}"#,
+        )
+        .unwrap();
+        assert_eq!(result.mode_used, None);
+    }
+
+    #[test]
+    fn test_extract_options_with_schema_json() {
+        let options = ExtractOptions::new()
+            .with_schema_json(r#"{"total": {"type": "number"}}"#)
+            .unwrap();
+        let schema = options.schema.unwrap();
+        assert_eq!(schema["total"]["type"], "number");
+
+        let err = ExtractOptions::new()
+            .with_schema_json("not json")
+            .unwrap_err();
+        assert!(matches!(err, RenamedError::Serialization { .. }));
+    }
+
+    #[test]
+    fn test_extract_options_with_schema_value() {
+        let options = ExtractOptions::new()
+            .with_schema_value(serde_json::json!({"total": {"type": "number"}}))
+            .unwrap();
+        assert_eq!(options.schema.unwrap()["total"]["type"], "number");
+
+        let err = ExtractOptions::new()
+            .with_schema_value(serde_json::json!(["total"]))
+            .unwrap_err();
+        assert!(matches!(err, RenamedError::Serialization { .. }));
+    }
+
+    #[cfg(feature = "jsonschema")]
+    #[test]
+    fn test_extract_result_validate_against() {
+        let schema: HashMap<String, serde_json::Value> = serde_json::from_str(
+            r#"{
+                "type": "object",
+                "properties": {"total": {"type": "number"}},
+                "required": ["total", "vendor"]
+            }"#,
+        )
+        .unwrap();
+
+        let valid: ExtractResult =
+            serde_json::from_str(r#"{"data": {"total": 10, "vendor": "ACME"}, "confidence": 1.0}"#)
+                .unwrap();
+        assert!(valid.validate_against(&schema).is_ok());
+
+        let invalid: ExtractResult =
+            serde_json::from_str(r#"{"data": {"total": "ten"}, "confidence": 1.0}"#).unwrap();
+        match invalid.validate_against(&schema) {
+            Err(RenamedError::InvalidValue { details, .. }) => {
+                let errors = &details.unwrap()["errors"];
+                assert_eq!(errors.as_array().unwrap().len(), 2);
+            }
+            other => panic!("Expected InvalidValue error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "jsonschema")]
+    #[test]
+    fn test_validate_against_field_descriptions() {
+        let schema: HashMap<String, serde_json::Value> =
+            serde_json::from_str(r#"{"total": "number", "vendor": "Name of the seller"}"#).unwrap();
+
+        let valid: ExtractResult =
+            serde_json::from_str(r#"{"data": {"total": 10, "vendor": 3}, "confidence": 1.0}"#)
+                .unwrap();
+        assert!(valid.validate_against(&schema).is_ok());
+
+        let invalid: ExtractResult =
+            serde_json::from_str(r#"{"data": {"total": "ten"}, "confidence": 1.0}"#).unwrap();
+        assert!(matches!(
+            invalid.validate_against(&schema),
+            Err(RenamedError::InvalidValue { .. })
+        ));
+    }
+
     #[test]
     fn test_job_status_is_in_progress() {
         assert!(JobStatus::Pending.is_in_progress());
@@ -317,10 +1890,181 @@ mod tests {
         assert!(!JobStatus::Failed.is_in_progress());
     }
 
+    #[test]
+    fn test_merge_options_per_call_wins() {
+        let defaults = PdfSplitOptions::new()
+            .with_mode(SplitMode::Pages)
+            .with_pages_per_split(5);
+        let per_call = PdfSplitOptions::new().with_pages_per_split(2);
+
+        let merged = merge_options(Some(per_call), Some(&defaults)).unwrap();
+        assert_eq!(merged.mode, Some(SplitMode::Pages));
+        assert_eq!(merged.pages_per_split, Some(2));
+
+        let merged = merge_options(None, Some(&defaults)).unwrap();
+        assert_eq!(merged.pages_per_split, Some(5));
+
+        assert!(merge_options::<RenameOptions>(None, None).is_none());
+    }
+
+    #[test]
+    fn test_download_options_builder() {
+        let options = DownloadOptions::new()
+            .with_resume(true)
+            .with_expected_size(2048);
+
+        assert!(options.resume);
+        assert_eq!(options.expected_size, Some(2048));
+        assert!(!DownloadOptions::default().resume);
+    }
+
+    #[test]
+    fn test_split_document_inline_content() {
+        let doc: SplitDocument = serde_json::from_str(
+            r#"{"index": 0, "filename": "a.pdf", "pages": "1", "downloadUrl": "", "size": 4, "content": "JVBERg=="}"#,
+        )
+        .unwrap();
+        assert_eq!(doc.content.as_deref(), Some(&b"%PDF"[..]));
+
+        let json = serde_json::to_value(&doc).unwrap();
+        assert_eq!(json["content"], "JVBERg==");
+
+        let invalid = serde_json::from_str::<SplitDocument>(
+            r#"{"index": 0, "filename": "a.pdf", "pages": "1", "downloadUrl": "", "size": 4, "content": "not base64!"}"#,
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_split_document_page_range() {
+        let doc = |pages: &str| SplitDocument {
+            index: 0,
+            filename: "a.pdf".to_string(),
+            pages: pages.to_string(),
+            download_url: String::new(),
+            size: 0,
+            content: None,
+        };
+
+        assert_eq!(doc("5").page_range().unwrap(), (5, 5));
+        assert_eq!(doc(" 1 - 3 ").page_range().unwrap(), (1, 3));
+        assert_eq!(doc("1-3").page_count(), 3);
+        assert_eq!(doc("5").page_count(), 1);
+
+        for malformed in ["", "abc", "3-1", "0-2", "1-", "1-2-3"] {
+            match doc(malformed).page_range() {
+                Err(RenamedError::InvalidValue { value, .. }) => {
+                    assert_eq!(value.as_deref(), Some(malformed));
+                }
+                other => panic!("{:?} should be rejected, got {:?}", malformed, other),
+            }
+            assert_eq!(doc(malformed).page_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_pdf_split_result_verify_coverage() {
+        let result = |total_pages: u32, pages: &[&str]| PdfSplitResult {
+            original_filename: "scan.pdf".to_string(),
+            documents: pages
+                .iter()
+                .enumerate()
+                .map(|(i, p)| SplitDocument {
+                    index: i as u32,
+                    filename: format!("{}.pdf", i),
+                    pages: p.to_string(),
+                    download_url: String::new(),
+                    size: 0,
+                    content: None,
+                })
+                .collect(),
+            total_pages,
+            credits_used: None,
+            mode_used: None,
+        };
+
+        assert!(result(6, &["4-6", "1-2", "3"]).verify_coverage().is_ok());
+
+        match result(8, &["1-2", "2-3", "5-6", "9"]).verify_coverage() {
+            Err(RenamedError::InvalidValue {
+                message, details, ..
+            }) => {
+                assert!(message.contains("missing pages 4, 7-8"), "{}", message);
+                let details = details.unwrap();
+                assert_eq!(details["gaps"], serde_json::json!([[4, 4], [7, 8]]));
+                assert_eq!(details["overlaps"], serde_json::json!([[2, 2]]));
+                assert_eq!(details["out_of_range"], serde_json::json!([[9, 9]]));
+            }
+            other => panic!("Expected InvalidValue error, got {:?}", other),
+        }
+
+        assert!(matches!(
+            result(2, &["1", "x"]).verify_coverage(),
+            Err(RenamedError::InvalidValue { value: Some(_), .. })
+        ));
+    }
+
+    #[test]
+    fn test_filename_collisions_compare_local_filenames() {
+        let result: PdfSplitResult = serde_json::from_str(
+            r#"{"originalFilename": "scan.pdf", "totalPages": 2, "documents": [
+                {"index": 0, "filename": "a:b.pdf", "pages": "1", "downloadUrl": "", "size": 0},
+                {"index": 1, "filename": "a_b.pdf", "pages": "2", "downloadUrl": "", "size": 0}]}"#,
+        )
+        .unwrap();
+
+        assert!(result.has_filename_collisions());
+        assert_eq!(
+            result.deduplicated_filenames(),
+            vec!["a_b.pdf", "a_b_2.pdf"]
+        );
+    }
+
+    #[test]
+    fn test_deduplicate_filenames() {
+        let names = [
+            "a.pdf", "a.pdf", "a_2.pdf", "a.pdf", "notes", "notes", ".env", ".env",
+        ];
+        assert_eq!(
+            deduplicate_filenames(names.iter().map(|n| n.to_string())),
+            vec!["a.pdf", "a_3.pdf", "a_2.pdf", "a_4.pdf", "notes", "notes_2", ".env", ".env_2"]
+        );
+    }
+
     #[test]
     fn test_split_mode_display() {
         assert_eq!(SplitMode::Auto.to_string(), "auto");
         assert_eq!(SplitMode::Pages.to_string(), "pages");
         assert_eq!(SplitMode::Blank.to_string(), "blank");
     }
+
+    #[test]
+    fn test_split_mode_and_job_status_from_str() {
+        assert_eq!("Pages".parse::<SplitMode>().unwrap(), SplitMode::Pages);
+        assert_eq!(SplitMode::try_from(" AUTO ").unwrap(), SplitMode::Auto);
+        for mode in [SplitMode::Auto, SplitMode::Pages, SplitMode::Blank] {
+            assert_eq!(mode.to_string().parse::<SplitMode>().unwrap(), mode);
+        }
+
+        assert_eq!(
+            JobStatus::try_from("Completed").unwrap(),
+            JobStatus::Completed
+        );
+        assert_eq!(
+            "processing".parse::<JobStatus>().unwrap(),
+            JobStatus::Processing
+        );
+
+        match "chapters".parse::<SplitMode>() {
+            Err(RenamedError::InvalidValue { message, value, .. }) => {
+                assert_eq!(
+                    message,
+                    "Unknown split mode 'chapters', expected one of: auto, pages, blank"
+                );
+                assert_eq!(value.as_deref(), Some("chapters"));
+            }
+            other => panic!("Expected InvalidValue error, got {:?}", other),
+        }
+        assert!(JobStatus::try_from("done").is_err());
+    }
 }
@@ -0,0 +1,114 @@
+//! Local inspection of PDF files.
+//!
+//! The API has no endpoint that reports a file's page count, so it is read
+//! from the PDF's page tree without uploading the file.
+
+/// Bytes the PDF syntax treats as whitespace.
+const WHITESPACE: &[u8] = b"\0\t\n\x0c\r ";
+
+/// Bytes that end a name or number token.
+const DELIMITERS: &[u8] = b"()<>[]{}/%";
+
+/// Returns the number of pages in a PDF, read from the root of its page tree.
+///
+/// The root is the `/Type /Pages` object without a `/Parent`; when an
+/// incremental update redefines it, the last definition wins. Returns `None`
+/// if no root is stored as plain text, e.g. when the file keeps it in a
+/// compressed object stream.
+pub(crate) fn page_count(pdf: &[u8]) -> Option<u32> {
+    objects(pdf)
+        .filter(|body| values(body, b"/Type").any(|v| v == b"/Pages"))
+        .filter(|body| values(body, b"/Parent").next().is_none())
+        .filter_map(|body| {
+            let count = values(body, b"/Count").next()?;
+            std::str::from_utf8(count).ok()?.parse().ok()
+        })
+        .last()
+}
+
+/// Iterates over the bodies of the file's indirect objects, each ending at
+/// its `endobj` keyword.
+fn objects(pdf: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = pdf;
+    std::iter::from_fn(move || {
+        let end = find(rest, b"endobj")?;
+        let body = &rest[..end];
+        rest = &rest[end + b"endobj".len()..];
+        Some(body)
+    })
+}
+
+/// Iterates over the token following each occurrence of the name `key`.
+fn values<'a>(body: &'a [u8], key: &'static [u8]) -> impl Iterator<Item = &'a [u8]> {
+    let mut rest = body;
+    std::iter::from_fn(move || loop {
+        let at = find(rest, key)?;
+        rest = &rest[at + key.len()..];
+        // A longer name that merely starts with `key`, e.g. `/TypeX`
+        if rest.first().is_some_and(|&b| is_regular(b)) {
+            continue;
+        }
+
+        let start = rest
+            .iter()
+            .position(|b| !WHITESPACE.contains(b))
+            .unwrap_or(rest.len());
+        let token = &rest[start..];
+        // A name keeps its leading `/`, which is otherwise a delimiter
+        let len = token
+            .iter()
+            .skip(1)
+            .position(|&b| !is_regular(b))
+            .map_or(token.len(), |n| n + 1);
+        return Some(&token[..len]);
+    })
+}
+
+/// Returns true for bytes that continue a name or number token.
+fn is_regular(b: u8) -> bool {
+    !WHITESPACE.contains(&b) && !DELIMITERS.contains(&b)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_count_reads_page_tree_root() {
+        let pdf = b"%PDF-1.4
+1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj
+2 0 obj << /Type /Pages /Kids [3 0 R 4 0 R] /Count 5 >> endobj
+3 0 obj << /Type/Pages/Parent 2 0 R/Kids [5 0 R]/Count 4 >> endobj
+4 0 obj << /Type /Page /Parent 2 0 R >> endobj
+5 0 obj << /Type /Outlines /Count 9 >> endobj
+%%EOF";
+        assert_eq!(page_count(pdf), Some(5));
+    }
+
+    #[test]
+    fn test_page_count_uses_latest_revision() {
+        let pdf = b"%PDF-1.4
+2 0 obj << /Type /Pages /Kids [3 0 R] /Count 7 >> endobj
+%%EOF
+2 0 obj << /Type /Pages /Kids [3 0 R] /Count 6 >> endobj
+%%EOF";
+        assert_eq!(page_count(pdf), Some(6));
+    }
+
+    #[test]
+    fn test_page_count_without_page_tree() {
+        assert_eq!(page_count(b"%PDF-1.5"), None);
+        assert_eq!(
+            page_count(b"1 0 obj << /Type /Page /Count 3 >> endobj"),
+            None
+        );
+        assert_eq!(
+            page_count(b"1 0 obj << /Type /PagesX /Count 3 >> endobj"),
+            None
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Rate-limit budget reported by the API.
+//!
+//! The API sends `X-RateLimit-*` headers on every response, not only on 429s.
+//! The client caches the most recent values so callers can slow down before
+//! hitting the limit; see
+//! [`RenamedClient::last_rate_limit()`](crate::RenamedClient::last_rate_limit).
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+
+use crate::maintenance::utc_time;
+
+/// Header carrying the request limit for the current window.
+const LIMIT_HEADER: &str = "X-RateLimit-Limit";
+
+/// Header carrying the requests remaining in the current window.
+const REMAINING_HEADER: &str = "X-RateLimit-Remaining";
+
+/// Header carrying when the current window resets.
+const RESET_HEADER: &str = "X-RateLimit-Reset";
+
+/// Values above this are Unix timestamps rather than seconds from now.
+const EPOCH_THRESHOLD_SECS: u64 = 1_000_000_000;
+
+/// Rate-limit budget from the most recent API response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Maximum number of requests allowed in the current window.
+    pub limit: Option<u32>,
+
+    /// Requests remaining in the current window.
+    pub remaining: Option<u32>,
+
+    /// When the current window resets.
+    pub reset_at: Option<SystemTime>,
+}
+
+impl RateLimitInfo {
+    /// Parses rate-limit headers, returning `None` if none are present.
+    ///
+    /// `X-RateLimit-Reset` is documented as an ISO 8601 timestamp such as
+    /// `2024-01-15T10:30:00Z`; a Unix timestamp or a number of seconds from
+    /// `now` is accepted as a fallback. Values too large to represent are
+    /// ignored.
+    pub(crate) fn from_headers(headers: &HeaderMap, now: SystemTime) -> Option<Self> {
+        let number = |name: &str| -> Option<u64> {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+        };
+
+        let limit = number(LIMIT_HEADER).map(|v| u32::try_from(v).unwrap_or(u32::MAX));
+        let remaining = number(REMAINING_HEADER).map(|v| u32::try_from(v).unwrap_or(u32::MAX));
+        let reset_at = headers
+            .get(RESET_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_rfc3339(v.trim()))
+            .or_else(|| {
+                number(RESET_HEADER).and_then(|secs| {
+                    let base = if secs >= EPOCH_THRESHOLD_SECS {
+                        UNIX_EPOCH
+                    } else {
+                        now
+                    };
+                    base.checked_add(Duration::from_secs(secs))
+                })
+            });
+
+        if limit.is_none() && remaining.is_none() && reset_at.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            limit,
+            remaining,
+            reset_at,
+        })
+    }
+}
+
+/// Parses an RFC 3339 timestamp (`2024-01-15T10:30:00.5+01:00`).
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    let field = |range: std::ops::Range<usize>| -> Option<u32> {
+        let digits = value.get(range)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+    let bytes = value.as_bytes();
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+    let time = utc_time(
+        field(0..4)?,
+        field(5..7)?,
+        field(8..10)?,
+        field(11..13)?,
+        field(14..16)?,
+        field(17..19)?,
+    )?;
+
+    let mut rest = &value[19..];
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None;
+        }
+        // Digits past nanosecond precision are dropped
+        let digits = &fraction[..len.min(9)];
+        nanos = digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32);
+        rest = &fraction[len..];
+    }
+    let time = time.checked_add(Duration::from_nanos(u64::from(nanos)))?;
+
+    match rest {
+        "Z" | "z" => Some(time),
+        _ => {
+            let ahead = match rest.as_bytes().first()? {
+                b'+' => true,
+                b'-' => false,
+                _ => return None,
+            };
+            let offset = rest.get(1..)?;
+            if offset.len() != 5 || offset.as_bytes()[2] != b':' {
+                return None;
+            }
+            let (hours, minutes) = (
+                offset[..2].parse::<u64>().ok()?,
+                offset[3..].parse::<u64>().ok()?,
+            );
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = Duration::from_secs(hours * 3_600 + minutes * 60);
+            // A local time ahead of UTC means the instant is earlier
+            if ahead {
+                time.checked_sub(offset)
+            } else {
+                time.checked_add(offset)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimitInfo::from_headers(&headers, now), None);
+
+        headers.insert(LIMIT_HEADER, HeaderValue::from_static("100"));
+        headers.insert(REMAINING_HEADER, HeaderValue::from_static("7"));
+        headers.insert(RESET_HEADER, HeaderValue::from_static("30"));
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers, now),
+            Some(RateLimitInfo {
+                limit: Some(100),
+                remaining: Some(7),
+                reset_at: Some(now + Duration::from_secs(30)),
+            })
+        );
+
+        headers.insert(RESET_HEADER, HeaderValue::from_static("1700000060"));
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers, now).unwrap().reset_at,
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_060))
+        );
+    }
+
+    #[test]
+    fn test_from_headers_iso_reset() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let reset_at = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(RESET_HEADER, HeaderValue::from_static(value));
+            RateLimitInfo::from_headers(&headers, now).and_then(|info| info.reset_at)
+        };
+
+        // 2023-11-14T22:14:20Z is 1_700_000_060
+        let expected = UNIX_EPOCH + Duration::from_secs(1_700_000_060);
+        assert_eq!(reset_at("2023-11-14T22:14:20Z"), Some(expected));
+        assert_eq!(reset_at("2023-11-14T23:14:20+01:00"), Some(expected));
+        assert_eq!(reset_at("2023-11-14T21:44:20-00:30"), Some(expected));
+        assert_eq!(
+            reset_at("2023-11-14T22:14:20.250Z"),
+            Some(expected + Duration::from_millis(250))
+        );
+
+        assert_eq!(reset_at("2023-11-14T25:14:20Z"), None);
+        assert_eq!(reset_at("2023-11-14T22:14:20"), None);
+    }
+
+    #[test]
+    fn test_from_headers_overflowing_reset() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut headers = HeaderMap::new();
+        headers.insert(LIMIT_HEADER, HeaderValue::from_static("100"));
+        headers.insert(
+            RESET_HEADER,
+            HeaderValue::from_static("18446744073709551615"),
+        );
+
+        // The unusable reset is dropped rather than panicking
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers, now),
+            Some(RateLimitInfo {
+                limit: Some(100),
+                remaining: None,
+                reset_at: None,
+            })
+        );
+    }
+}
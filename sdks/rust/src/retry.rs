@@ -0,0 +1,235 @@
+//! Retry configuration for failed requests.
+//!
+//! This module provides [`RetryPolicy`], which selects the classes of failures
+//! the client retries automatically, and the [`RetryStrategy`] trait for taking
+//! full control of retry decisions and delays.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::RenamedError;
+
+/// Selects which classes of failures are retried automatically.
+///
+/// The default retries network failures and timeouts only, matching the
+/// client's historical behavior. Rate-limit (429) and server (5xx) errors are
+/// returned immediately unless enabled.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use renamed::{RenamedClient, RetryPolicy};
+///
+/// // Handle rate limits in your own queue, but keep retrying flaky connections
+/// let client = RenamedClient::builder("rt_your_api_key")
+///     .retry_on(RetryPolicy::default().with_rate_limit(false))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retry connection and other transport failures.
+    pub network: bool,
+    /// Retry requests that timed out.
+    pub timeout: bool,
+    /// Retry `429 Too Many Requests` responses.
+    pub rate_limit: bool,
+    /// Retry `5xx` server error responses.
+    pub server_error: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            network: true,
+            timeout: true,
+            rate_limit: false,
+            server_error: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that retries every retryable failure class.
+    pub fn all() -> Self {
+        Self {
+            network: true,
+            timeout: true,
+            rate_limit: true,
+            server_error: true,
+        }
+    }
+
+    /// Creates a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            network: false,
+            timeout: false,
+            rate_limit: false,
+            server_error: false,
+        }
+    }
+
+    /// Sets whether network failures are retried.
+    pub fn with_network(mut self, enabled: bool) -> Self {
+        self.network = enabled;
+        self
+    }
+
+    /// Sets whether timeouts are retried.
+    pub fn with_timeout(mut self, enabled: bool) -> Self {
+        self.timeout = enabled;
+        self
+    }
+
+    /// Sets whether rate-limit (429) responses are retried.
+    pub fn with_rate_limit(mut self, enabled: bool) -> Self {
+        self.rate_limit = enabled;
+        self
+    }
+
+    /// Sets whether server error (5xx) responses are retried.
+    pub fn with_server_error(mut self, enabled: bool) -> Self {
+        self.server_error = enabled;
+        self
+    }
+
+    /// Returns true if the policy allows retrying the given error.
+    pub fn should_retry(&self, error: &RenamedError) -> bool {
+        match error {
+            RenamedError::Network { .. } => self.network,
+            RenamedError::Timeout { .. } => self.timeout,
+            RenamedError::RateLimit { .. } => self.rate_limit,
+            RenamedError::Api { status_code, .. } => self.server_error && *status_code >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// Decides whether and when a failed request is retried.
+///
+/// Implement this trait to plug a custom retry policy (e.g. decorrelated jitter)
+/// into the client via [`RenamedClientBuilder::retry_strategy()`](crate::RenamedClientBuilder::retry_strategy).
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use renamed::{RenamedClient, RenamedError, RetryStrategy};
+///
+/// #[derive(Debug)]
+/// struct FixedDelay;
+///
+/// impl RetryStrategy for FixedDelay {
+///     fn next_delay(&self, attempt: u32, error: &RenamedError) -> Option<Duration> {
+///         let retryable = matches!(error, RenamedError::Network { .. });
+///         (retryable && attempt < 5).then(|| Duration::from_secs(1))
+///     }
+/// }
+///
+/// let client = RenamedClient::builder("rt_your_api_key")
+///     .retry_strategy(Arc::new(FixedDelay))
+///     .build();
+/// ```
+pub trait RetryStrategy: Send + Sync + fmt::Debug {
+    /// Returns how long to wait before retrying, or `None` to stop retrying.
+    ///
+    /// `attempt` is the number of retries already made for this request, so it
+    /// is `0` after the first failure.
+    fn next_delay(&self, attempt: u32, error: &RenamedError) -> Option<Duration>;
+}
+
+/// The built-in retry strategy.
+///
+/// Retries errors allowed by its [`RetryPolicy`] up to `max_retries` times,
+/// with exponential backoff (100ms, 200ms, 400ms, ...). Rate-limit errors that
+/// carry a `retryAfter` wait that long instead.
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultRetryStrategy {
+    max_retries: u32,
+    policy: RetryPolicy,
+}
+
+impl DefaultRetryStrategy {
+    /// Creates a strategy retrying up to `max_retries` times under `policy`.
+    pub fn new(max_retries: u32, policy: RetryPolicy) -> Self {
+        Self {
+            max_retries,
+            policy,
+        }
+    }
+}
+
+impl RetryStrategy for DefaultRetryStrategy {
+    fn next_delay(&self, attempt: u32, error: &RenamedError) -> Option<Duration> {
+        if attempt >= self.max_retries || !self.policy.should_retry(error) {
+            return None;
+        }
+
+        match error {
+            RenamedError::RateLimit {
+                retry_after: Some(seconds),
+                ..
+            } => Some(Duration::from_secs(u64::from(*seconds))),
+            _ => Some(Duration::from_millis(100 * (1 << attempt))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_retries_transport_failures_only() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.should_retry(&RenamedError::Timeout {
+            message: "Request timed out".into(),
+            elapsed: None,
+        }));
+        assert!(!policy.should_retry(&RenamedError::from_http_status(429, None)));
+        assert!(!policy.should_retry(&RenamedError::from_http_status(503, None)));
+    }
+
+    #[test]
+    fn test_all_policy_retries_server_errors_not_client_errors() {
+        let policy = RetryPolicy::all();
+
+        assert!(policy.should_retry(&RenamedError::from_http_status(429, None)));
+        assert!(policy.should_retry(&RenamedError::from_http_status(503, None)));
+        assert!(!policy.should_retry(&RenamedError::from_http_status(404, None)));
+        assert!(!policy.should_retry(&RenamedError::from_http_status(401, None)));
+        assert!(!RetryPolicy::none().should_retry(&RenamedError::Network {
+            message: "Connection failed".into(),
+            source: None,
+            elapsed: None,
+        }));
+    }
+
+    #[test]
+    fn test_default_strategy_delays() {
+        let strategy = DefaultRetryStrategy::new(3, RetryPolicy::all());
+        let timeout = RenamedError::Timeout {
+            message: "Request timed out".into(),
+            elapsed: None,
+        };
+
+        assert_eq!(
+            strategy.next_delay(0, &timeout),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            strategy.next_delay(2, &timeout),
+            Some(Duration::from_millis(400))
+        );
+        assert_eq!(strategy.next_delay(3, &timeout), None);
+
+        let rate_limit =
+            RenamedError::from_http_status(429, Some(r#"{"error": "Slow down", "retryAfter": 3}"#));
+        assert_eq!(
+            strategy.next_delay(0, &rate_limit),
+            Some(Duration::from_secs(3))
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Client-wide retry budget.
+//!
+//! Per-request retries multiply load during an outage: many concurrent
+//! requests each retrying several times can overwhelm a struggling API. The
+//! budget is a token bucket shared by every request of a client: each retry
+//! takes a token, tokens refill at a fixed rate, and once the bucket is empty
+//! requests fail with their last error instead of retrying.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Token bucket of retries left, refilled at `per_second` tokens a second.
+#[derive(Debug)]
+pub(crate) struct RetryBudget {
+    /// Retries allowed per second, which is also the bucket's capacity.
+    per_second: f64,
+
+    state: Mutex<BudgetState>,
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RetryBudget {
+    /// Creates a full budget allowing `per_second` retries per second.
+    pub fn new(per_second: u32) -> Self {
+        let per_second = f64::from(per_second);
+        Self {
+            per_second,
+            state: Mutex::new(BudgetState {
+                tokens: per_second,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes one retry from the budget, returning false if none is left.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let now = Instant::now();
+        let refill = now.duration_since(state.refilled_at).as_secs_f64() * self.per_second;
+        state.tokens = (state.tokens + refill).min(self.per_second);
+        state.refilled_at = now;
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_budget_is_exhausted_then_refills() {
+        let budget = RetryBudget::new(2);
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(600));
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+    }
+
+    #[test]
+    fn test_zero_budget_never_retries() {
+        assert!(!RetryBudget::new(0).try_acquire());
+    }
+}
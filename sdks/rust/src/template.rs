@@ -0,0 +1,107 @@
+//! Local rendering of rename templates for previews.
+
+use std::collections::HashMap;
+
+use crate::error::{RenamedError, Result};
+
+/// Renders a rename template against sample placeholder values.
+///
+/// Each `{name}` placeholder is replaced by `values["name"]`, so a UI can show
+/// what a template like `"{date}_{vendor}"` will produce before it is saved or
+/// sent with [`RenameOptions::with_template()`](crate::RenameOptions::with_template).
+/// Rendering happens locally: no request is made and no credits are spent.
+/// The server may still format extracted values differently (e.g. dates).
+///
+/// # Errors
+///
+/// Returns [`RenamedError::InvalidValue`] if a placeholder has no value, with
+/// the missing names under `details["missing"]`, or if a `{` is not closed.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// let values = HashMap::from([
+///     ("date".to_string(), "2024-03-01".to_string()),
+///     ("vendor".to_string(), "Acme".to_string()),
+/// ]);
+/// let preview = renamed::render_template("{date}_{vendor}", &values)?;
+/// assert_eq!(preview, "2024-03-01_Acme");
+/// # Ok::<(), renamed::RenamedError>(())
+/// ```
+pub fn render_template(template: &str, values: &HashMap<String, String>) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut missing = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            return Err(RenamedError::InvalidValue {
+                message: format!("Unclosed placeholder in template: {:?}", template).into(),
+                value: Some(template.to_string()),
+                details: None,
+            });
+        };
+
+        let name = rest[start + 1..start + len].trim();
+        match values.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => missing.push(name.to_string()),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    rendered.push_str(rest);
+
+    if !missing.is_empty() {
+        return Err(RenamedError::InvalidValue {
+            message: format!("No sample value for placeholders: {}", missing.join(", ")).into(),
+            value: Some(template.to_string()),
+            details: Some(HashMap::from([(
+                "missing".to_string(),
+                serde_json::json!(missing),
+            )])),
+        });
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> HashMap<String, String> {
+        HashMap::from([
+            ("date".to_string(), "2024-03-01".to_string()),
+            ("vendor".to_string(), "Acme".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_template() {
+        assert_eq!(
+            render_template("{date}_{ vendor }_invoice", &values()).unwrap(),
+            "2024-03-01_Acme_invoice"
+        );
+        assert_eq!(render_template("plain", &values()).unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_render_template_reports_missing_and_unclosed_placeholders() {
+        match render_template("{date}_{type}_{amount}", &values()) {
+            Err(RenamedError::InvalidValue { details, .. }) => {
+                assert_eq!(
+                    details.unwrap()["missing"],
+                    serde_json::json!(["type", "amount"])
+                );
+            }
+            other => panic!("expected invalid value error, got {:?}", other),
+        }
+
+        assert!(matches!(
+            render_template("{date", &values()),
+            Err(RenamedError::InvalidValue { .. })
+        ));
+    }
+}
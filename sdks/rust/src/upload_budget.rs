@@ -0,0 +1,79 @@
+//! Client-wide cap on the memory held by uploads in flight.
+//!
+//! Uploads from a path or from bytes are buffered in memory while they are
+//! sent, so concurrent uploads of large files can use a lot of it. The budget
+//! is a semaphore shared by every request of a client: each upload holds
+//! permits for its size until it finishes, and uploads that don't fit wait.
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Bytes covered by one permit, keeping permit counts within `u32`.
+const PERMIT_BYTES: u64 = 1024;
+
+/// Semaphore counting the upload bytes in flight, in [`PERMIT_BYTES`] units.
+#[derive(Debug)]
+pub(crate) struct UploadBudget {
+    semaphore: Semaphore,
+
+    /// Permits in the budget; larger uploads take all of them.
+    total_permits: u32,
+}
+
+impl UploadBudget {
+    /// Creates a budget allowing `max_bytes` of uploads in flight.
+    pub fn new(max_bytes: u64) -> Self {
+        let max_permits = u64::try_from(Semaphore::MAX_PERMITS).unwrap_or(u64::MAX);
+        let total_permits = Self::permits_for(max_bytes).clamp(1, max_permits.min(u32::MAX.into()));
+        let total_permits = u32::try_from(total_permits).unwrap_or(u32::MAX);
+        Self {
+            semaphore: Semaphore::new(total_permits as usize),
+            total_permits,
+        }
+    }
+
+    /// Waits until an upload of `bytes` fits in the budget.
+    ///
+    /// The budget is released when the returned permit is dropped. An upload
+    /// larger than the whole budget waits for it to be empty, then runs alone.
+    pub async fn acquire(&self, bytes: u64) -> SemaphorePermit<'_> {
+        let permits = u32::try_from(Self::permits_for(bytes))
+            .unwrap_or(u32::MAX)
+            .min(self.total_permits);
+        self.semaphore
+            .acquire_many(permits)
+            .await
+            .expect("upload budget semaphore is never closed")
+    }
+
+    fn permits_for(bytes: u64) -> u64 {
+        bytes.div_ceil(PERMIT_BYTES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_uploads_wait_for_budget() {
+        let budget = UploadBudget::new(4 * 1024);
+
+        let first = budget.acquire(3 * 1024).await;
+        let blocked = tokio::time::timeout(Duration::from_millis(50), budget.acquire(2 * 1024));
+        assert!(blocked.await.is_err());
+
+        drop(first);
+        let _second = budget.acquire(2 * 1024).await;
+    }
+
+    #[tokio::test]
+    async fn test_oversized_upload_takes_whole_budget() {
+        let budget = UploadBudget::new(1024);
+
+        let oversized = budget.acquire(10 * 1024 * 1024).await;
+        let blocked = tokio::time::timeout(Duration::from_millis(50), budget.acquire(1));
+        assert!(blocked.await.is_err());
+        drop(oversized);
+    }
+}